@@ -0,0 +1,166 @@
+// Example: Load-test the log ingestion pipeline
+//
+// Drives POST /api/v1/logs at a configurable concurrency and volume, reporting
+// sustained throughput and latency percentiles so the batching/queue subsystems
+// can be regression-tested under load.
+//
+// Run with:
+//   cargo run --release --example bench_ingest -- \
+//     --url http://localhost:8080 --threads 32 --requests 10000 [--wait]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use serde_json::json;
+
+/// Parsed command-line configuration.
+struct Config {
+    url: String,
+    threads: usize,
+    requests: usize,
+    wait: bool,
+}
+
+impl Config {
+    /// Parse flags from the process arguments, falling back to sensible defaults.
+    fn from_args() -> Self {
+        let mut cfg = Config {
+            url: "http://localhost:8080".to_string(),
+            threads: 16,
+            requests: 1000,
+            wait: false,
+        };
+
+        let args: Vec<String> = std::env::args().collect();
+        let mut i = 1;
+        while i < args.len() {
+            match args[i].as_str() {
+                // A trailing flag with no value keeps its default rather than panicking.
+                "--url" => { if let Some(v) = args.get(i + 1) { cfg.url = v.clone(); } i += 2; }
+                "--threads" => { cfg.threads = args.get(i + 1).and_then(|v| v.parse().ok()).unwrap_or(cfg.threads); i += 2; }
+                "--requests" => { cfg.requests = args.get(i + 1).and_then(|v| v.parse().ok()).unwrap_or(cfg.requests); i += 2; }
+                "--wait" => { cfg.wait = true; i += 1; }
+                _ => { i += 1; }
+            }
+        }
+        cfg
+    }
+}
+
+#[tokio::main]
+async fn main() {
+    let cfg = Arc::new(Config::from_args());
+    let client = reqwest::Client::new();
+
+    eprintln!(
+        "⚡ Benchmarking {} ({} requests, {} concurrent, wait={})",
+        cfg.url, cfg.requests, cfg.threads, cfg.wait
+    );
+
+    let next = Arc::new(AtomicUsize::new(0));
+    let confirmed = Arc::new(AtomicUsize::new(0));
+    let start = Instant::now();
+
+    // Fan out `threads` workers that each pull the next request index until the
+    // total is exhausted, recording per-request latency.
+    let mut handles = Vec::with_capacity(cfg.threads);
+    for _ in 0..cfg.threads {
+        let client = client.clone();
+        let cfg = Arc::clone(&cfg);
+        let next = Arc::clone(&next);
+        let confirmed = Arc::clone(&confirmed);
+        handles.push(tokio::spawn(async move {
+            let mut latencies = Vec::new();
+            loop {
+                let i = next.fetch_add(1, Ordering::Relaxed);
+                if i >= cfg.requests {
+                    break;
+                }
+                let payload = json!({
+                    "event_type": "bench",
+                    "severity": "info",
+                    "data": { "seq": i, "source": "bench_ingest" }
+                });
+
+                let sent = Instant::now();
+                let resp = client
+                    .post(format!("{}/api/v1/logs", cfg.url))
+                    .json(&payload)
+                    .send()
+                    .await;
+                latencies.push(sent.elapsed().as_secs_f64() * 1000.0);
+
+                if cfg.wait {
+                    if let Ok(resp) = resp {
+                        if let Ok(body) = resp.json::<serde_json::Value>().await {
+                            if let Some(id) = body.get("id").and_then(|v| v.as_str()) {
+                                if wait_for_confirmation(&client, &cfg.url, id).await {
+                                    confirmed.fetch_add(1, Ordering::Relaxed);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+            latencies
+        }));
+    }
+
+    let mut latencies = Vec::new();
+    for handle in handles {
+        if let Ok(mut part) = handle.await {
+            latencies.append(&mut part);
+        }
+    }
+
+    let elapsed = start.elapsed().as_secs_f64();
+    report(&cfg, &mut latencies, confirmed.load(Ordering::Relaxed), elapsed);
+}
+
+/// Poll `GET /logs/{id}` until the log reaches `confirmed`/`finalized` or times out.
+async fn wait_for_confirmation(client: &reqwest::Client, url: &str, id: &str) -> bool {
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while Instant::now() < deadline {
+        if let Ok(resp) = client.get(format!("{}/api/v1/logs/{}", url, id)).send().await {
+            if let Ok(body) = resp.json::<serde_json::Value>().await {
+                match body.get("blockchain_status").and_then(|v| v.as_str()) {
+                    Some("confirmed") | Some("finalized") => return true,
+                    Some("failed") => return false,
+                    _ => {}
+                }
+            }
+        }
+        tokio::time::sleep(Duration::from_millis(500)).await;
+    }
+    false
+}
+
+/// Print throughput and latency percentiles to stdout.
+fn report(cfg: &Config, latencies: &mut [f64], confirmed: usize, elapsed: f64) {
+    latencies.sort_by(|a, b| a.partial_cmp(b).unwrap());
+    let count = latencies.len();
+    let throughput = count as f64 / elapsed;
+
+    println!("\n📊 Results");
+    println!("  requests:    {}", count);
+    println!("  duration:    {:.2}s", elapsed);
+    println!("  throughput:  {:.1} logs/s", throughput);
+    println!("  p50:         {:.2}ms", percentile(latencies, 50.0));
+    println!("  p95:         {:.2}ms", percentile(latencies, 95.0));
+    println!("  p99:         {:.2}ms", percentile(latencies, 99.0));
+    if cfg.wait {
+        let frac = if count > 0 { confirmed as f64 / count as f64 * 100.0 } else { 0.0 };
+        println!("  confirmed:   {}/{} ({:.1}%)", confirmed, count, frac);
+    }
+}
+
+/// Nearest-rank percentile over a pre-sorted slice.
+fn percentile(sorted: &[f64], pct: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = (pct / 100.0 * sorted.len() as f64).ceil() as usize;
+    let idx = rank.saturating_sub(1).min(sorted.len() - 1);
+    sorted[idx]
+}
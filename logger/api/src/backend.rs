@@ -0,0 +1,365 @@
+use anyhow::{Context, Result};
+use async_trait::async_trait;
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{
+    commitment_config::CommitmentConfig,
+    instruction::{AccountMeta, Instruction},
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::Signer,
+    transaction::Transaction,
+};
+use solana_transaction_status::TransactionStatus;
+
+use crate::models;
+use crate::solana::SolanaClient;
+
+/// Abstract anchoring backend.
+///
+/// Every way the service can anchor a log hash — the spl-memo client, an
+/// on-chain Anchor program, or an in-memory mock for tests — implements this
+/// trait, so `AppState` can hold an `Arc<dyn LogBackend>` chosen at startup
+/// without `#[cfg(test)]` divergence between production and test code.
+#[async_trait]
+pub trait LogBackend: Send + Sync {
+    /// Anchor a single log hash and return its transaction signature.
+    async fn submit_log_hash(&self, hash: &str) -> Result<String>;
+
+    /// Anchor a Merkle root covering a whole batch of logs.
+    async fn submit_log_root(&self, hex_root: &str) -> Result<String>;
+
+    /// Recover the Merkle root anchored by a batch transaction.
+    async fn get_log_root(&self, tx_signature: &str) -> Result<Option<String>>;
+
+    /// Recover the single hash anchored by a transaction.
+    async fn verify_log_hash(&self, tx_signature: &str) -> Result<Option<String>>;
+
+    /// Fetch confirmation status for a batch of signatures.
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>>;
+
+    /// Fetch confirmation status searching the full transaction history, so a
+    /// `None` entry means a signature is genuinely unknown, not merely aged out.
+    async fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>>;
+
+    /// Decode a confirmed transaction into auditable metadata (verbose verify).
+    async fn get_transaction_detail(
+        &self,
+        tx_signature: &str,
+    ) -> Result<Option<models::TransactionDetail>>;
+
+    /// Verify backend connectivity.
+    async fn health_check(&self) -> bool;
+
+    /// Payer/authority public key, as a string.
+    fn pubkey(&self) -> String;
+
+    /// Payer balance in lamports, when the backend tracks one.
+    fn get_balance(&self) -> Result<u64>;
+}
+
+#[async_trait]
+impl LogBackend for SolanaClient {
+    async fn submit_log_hash(&self, hash: &str) -> Result<String> {
+        SolanaClient::submit_log_hash(self, hash).await
+    }
+
+    async fn submit_log_root(&self, hex_root: &str) -> Result<String> {
+        SolanaClient::submit_log_root(self, hex_root).await
+    }
+
+    async fn get_log_root(&self, tx_signature: &str) -> Result<Option<String>> {
+        SolanaClient::get_log_root(self, tx_signature).await
+    }
+
+    async fn verify_log_hash(&self, tx_signature: &str) -> Result<Option<String>> {
+        SolanaClient::verify_log_hash(self, tx_signature).await
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        SolanaClient::get_signature_statuses(self, signatures).await
+    }
+
+    async fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        SolanaClient::get_signature_statuses_with_history(self, signatures).await
+    }
+
+    async fn get_transaction_detail(
+        &self,
+        tx_signature: &str,
+    ) -> Result<Option<models::TransactionDetail>> {
+        SolanaClient::get_transaction_detail(self, tx_signature).await
+    }
+
+    async fn health_check(&self) -> bool {
+        SolanaClient::health_check(self).await
+    }
+
+    fn pubkey(&self) -> String {
+        SolanaClient::pubkey(self).to_string()
+    }
+
+    fn get_balance(&self) -> Result<u64> {
+        SolanaClient::get_balance(self)
+    }
+}
+
+/// Backend that anchors hashes through a deployed on-chain Anchor program.
+///
+/// Instead of a memo, the instruction data is the raw 32-byte hash and the
+/// target account is a PDA derived from the program id and the hash, so each
+/// anchor lands in its own program-owned account rather than the transaction
+/// log. Root anchoring reuses the same instruction shape.
+pub struct AnchorLogBackend {
+    rpc_client: RpcClient,
+    keypair: Keypair,
+    program_id: Pubkey,
+}
+
+impl AnchorLogBackend {
+    /// Create a client targeting a deployed program at `program_id`.
+    pub fn new(rpc_url: &str, keypair_path: &str, program_id: Pubkey) -> Result<Self> {
+        let rpc_client = RpcClient::new_with_commitment(
+            rpc_url.to_string(),
+            CommitmentConfig::confirmed(),
+        );
+        let keypair = SolanaClient::load_keypair(keypair_path)
+            .context("Failed to load keypair")?;
+
+        Ok(Self { rpc_client, keypair, program_id })
+    }
+
+    /// Derive the per-hash PDA that stores the anchor.
+    fn anchor_pda(&self, hash_bytes: &[u8; 32]) -> Pubkey {
+        Pubkey::find_program_address(&[b"log", hash_bytes], &self.program_id).0
+    }
+
+    /// Submit the 32-byte payload to the program and confirm it.
+    fn anchor_bytes(&self, hash_bytes: [u8; 32]) -> Result<String> {
+        let recent_blockhash = self.rpc_client
+            .get_latest_blockhash()
+            .context("Failed to get recent blockhash")?;
+
+        let pda = self.anchor_pda(&hash_bytes);
+        let instruction = Instruction {
+            program_id: self.program_id,
+            accounts: vec![
+                AccountMeta::new(pda, false),
+                AccountMeta::new(self.keypair.pubkey(), true),
+            ],
+            data: hash_bytes.to_vec(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.keypair.pubkey()),
+            &[&self.keypair],
+            recent_blockhash,
+        );
+
+        let signature = self.rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .context("Failed to send transaction")?;
+
+        Ok(signature.to_string())
+    }
+}
+
+#[async_trait]
+impl LogBackend for AnchorLogBackend {
+    async fn submit_log_hash(&self, hash: &str) -> Result<String> {
+        self.anchor_bytes(models::decode_hash(hash)?)
+    }
+
+    async fn submit_log_root(&self, hex_root: &str) -> Result<String> {
+        self.anchor_bytes(models::decode_hash(hex_root)?)
+    }
+
+    async fn get_log_root(&self, tx_signature: &str) -> Result<Option<String>> {
+        // The instruction data is the raw root; decode it straight back.
+        self.verify_log_hash(tx_signature).await
+    }
+
+    async fn verify_log_hash(&self, tx_signature: &str) -> Result<Option<String>> {
+        use std::str::FromStr;
+
+        let signature = Signature::from_str(tx_signature)
+            .context("Invalid transaction signature")?;
+        let tx = self.rpc_client
+            .get_transaction(&signature, solana_client::rpc_config::RpcTransactionConfig {
+                encoding: Some(solana_transaction_status::UiTransactionEncoding::Base64),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            })
+            .context("Failed to fetch transaction")?;
+
+        // Decode the base64 transaction and pull the 32-byte anchor back out of
+        // our program's instruction data, the inverse of `anchor_bytes`.
+        let Some(decoded) = tx.transaction.transaction.decode() else {
+            return Ok(None);
+        };
+        let message = decoded.message;
+        let account_keys = message.static_account_keys();
+        for ix in message.instructions() {
+            let program_id = account_keys.get(ix.program_id_index as usize);
+            if program_id == Some(&self.program_id) && ix.data.len() == 32 {
+                return Ok(Some(hex::encode(&ix.data)));
+            }
+        }
+        Ok(None)
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        let statuses = self.rpc_client
+            .get_signature_statuses(signatures)
+            .context("Failed to fetch signature statuses")?;
+        Ok(statuses.value)
+    }
+
+    async fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        let statuses = self.rpc_client
+            .get_signature_statuses_with_history(signatures)
+            .context("Failed to fetch signature statuses with history")?;
+        Ok(statuses.value)
+    }
+
+    async fn get_transaction_detail(
+        &self,
+        tx_signature: &str,
+    ) -> Result<Option<models::TransactionDetail>> {
+        use std::str::FromStr;
+
+        let signature = Signature::from_str(tx_signature)
+            .context("Invalid transaction signature")?;
+        let tx = self.rpc_client
+            .get_transaction(&signature, solana_client::rpc_config::RpcTransactionConfig {
+                encoding: Some(solana_transaction_status::UiTransactionEncoding::JsonParsed),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            })
+            .context("Failed to fetch transaction")?;
+
+        let fee = tx.transaction.meta.as_ref().map(|m| m.fee);
+        Ok(Some(models::TransactionDetail {
+            slot: tx.slot,
+            block_time: tx.block_time,
+            confirmation_status: Some("confirmed".to_string()),
+            confirmations: None,
+            fee,
+            signer: Some(self.keypair.pubkey().to_string()),
+            recent_blockhash: None,
+            // The program anchors raw bytes rather than a memo string.
+            memo: None,
+        }))
+    }
+
+    async fn health_check(&self) -> bool {
+        self.rpc_client.get_version().is_ok()
+    }
+
+    fn pubkey(&self) -> String {
+        self.keypair.pubkey().to_string()
+    }
+
+    fn get_balance(&self) -> Result<u64> {
+        self.rpc_client
+            .get_balance(&self.keypair.pubkey())
+            .context("Failed to get balance")
+    }
+}
+
+/// In-memory backend used by tests and local development.
+///
+/// Implements the same trait as the real backends, so the rest of the service
+/// runs unchanged whether it talks to a cluster or to this mock.
+pub struct MockLogBackend;
+
+impl MockLogBackend {
+    pub fn new() -> Self {
+        Self
+    }
+}
+
+impl Default for MockLogBackend {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[async_trait]
+impl LogBackend for MockLogBackend {
+    async fn submit_log_hash(&self, _hash: &str) -> Result<String> {
+        Ok("mock_signature_12345".to_string())
+    }
+
+    async fn submit_log_root(&self, _hex_root: &str) -> Result<String> {
+        Ok("mock_signature_12345".to_string())
+    }
+
+    async fn get_log_root(&self, _tx_signature: &str) -> Result<Option<String>> {
+        Ok(None)
+    }
+
+    async fn verify_log_hash(&self, _tx_signature: &str) -> Result<Option<String>> {
+        Ok(Some("mock_hash_67890".to_string()))
+    }
+
+    async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        Ok(signatures.iter().map(|_| None).collect())
+    }
+
+    async fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<TransactionStatus>>> {
+        Ok(signatures.iter().map(|_| None).collect())
+    }
+
+    async fn get_transaction_detail(
+        &self,
+        _tx_signature: &str,
+    ) -> Result<Option<models::TransactionDetail>> {
+        Ok(Some(models::TransactionDetail {
+            slot: 0,
+            block_time: None,
+            confirmation_status: Some("confirmed".to_string()),
+            confirmations: Some(32),
+            fee: Some(5000),
+            signer: Some(self.pubkey()),
+            recent_blockhash: Some("11111111111111111111111111111111".to_string()),
+            memo: Some("mock_memo".to_string()),
+        }))
+    }
+
+    async fn health_check(&self) -> bool {
+        true
+    }
+
+    fn pubkey(&self) -> String {
+        "MockWa11et1111111111111111111111111111111111".to_string()
+    }
+
+    fn get_balance(&self) -> Result<u64> {
+        Ok(0)
+    }
+}
@@ -0,0 +1,119 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use tokio::sync::broadcast;
+
+use crate::db::Database;
+use crate::models::{self, LogStatusEvent, MerkleProof};
+use crate::backend::LogBackend;
+
+/// Default ceiling on how many logs a single batch covers.
+const DEFAULT_BATCH_SIZE: i64 = 256;
+
+/// Default timeout flush so a trickle of logs still gets anchored promptly.
+const DEFAULT_FLUSH_SECS: u64 = 5;
+
+/// Read `BATCH_SIZE` from the environment, falling back to [`DEFAULT_BATCH_SIZE`].
+fn batch_size() -> i64 {
+    std::env::var("BATCH_SIZE")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_BATCH_SIZE)
+}
+
+/// Read `BATCH_FLUSH_SECS` from the environment, falling back to [`DEFAULT_FLUSH_SECS`].
+fn flush_interval() -> Duration {
+    let secs = std::env::var("BATCH_FLUSH_SECS")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_FLUSH_SECS);
+    Duration::from_secs(secs)
+}
+
+/// Spawn the background batcher that anchors pending logs under Merkle roots.
+///
+/// Flushes run on each `BATCH_FLUSH_SECS` tick. A tick seals batches of up to
+/// `BATCH_SIZE` pending logs, and keeps sealing while a batch comes back full.
+/// Reaching `BATCH_SIZE` between ticks does not start an early flush. Each flush builds a Merkle tree over the logs' SHA-256 hashes, anchors
+/// the single root on-chain and stamps each log with its inclusion proof; a
+/// failed root submission marks the whole batch `failed` so it can be retried.
+pub fn spawn(db: Arc<Database>, solana: Arc<dyn LogBackend>, events: broadcast::Sender<LogStatusEvent>) {
+    tokio::spawn(async move {
+        let size = batch_size();
+        let mut ticker = tokio::time::interval(flush_interval());
+        loop {
+            ticker.tick().await;
+            // Drain greedily: keep sealing full batches before sleeping again so a
+            // burst doesn't wait one interval per batch.
+            loop {
+                match flush_once(&db, &solana, &events, size).await {
+                    Ok(drained) if drained >= size as usize => continue,
+                    Ok(_) => break,
+                    Err(e) => {
+                        log::error!("Batcher flush failed: {}", e);
+                        break;
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Drain and anchor a single batch; returns how many logs it covered.
+async fn flush_once(
+    db: &Database,
+    solana: &dyn LogBackend,
+    events: &broadcast::Sender<LogStatusEvent>,
+    size: i64,
+) -> anyhow::Result<usize> {
+    let logs = db.get_pending_logs(size).await?;
+    if logs.is_empty() {
+        return Ok(0);
+    }
+
+    // Decode each stored hex hash into a raw 32-byte Merkle leaf.
+    let mut leaves = Vec::with_capacity(logs.len());
+    for log in &logs {
+        leaves.push(models::decode_hash(&log.hash)?);
+    }
+
+    let log_ids: Vec<_> = logs.iter().map(|l| l.id).collect();
+    let root = hex::encode(models::merkle_root(&leaves));
+    // Claim atomically: another producer (a queue worker or the reconciler) may
+    // have selected the same pending logs, so only act on the rows we actually won.
+    let Some((batch, claimed)) = db.create_batch(&root, &log_ids).await? else {
+        return Ok(0);
+    };
+
+    log::info!("Anchoring batch {} of {} logs (root {})", batch.id, claimed.len(), root);
+
+    match solana.submit_log_root(&root).await {
+        Ok(signature) => {
+            let proofs: Vec<(_, MerkleProof)> = logs
+                .iter()
+                .enumerate()
+                .filter(|(_, log)| claimed.contains(&log.id))
+                .map(|(i, log)| (log.id, models::merkle_proof(&leaves, i)))
+                .collect();
+            db.finalize_batch(batch.id, &signature, &proofs).await?;
+            log::info!("Batch {} anchored: {}", batch.id, signature);
+
+            // Notify live subscribers that these logs reached the chain.
+            for log in logs.iter().filter(|l| claimed.contains(&l.id)) {
+                let _ = events.send(LogStatusEvent {
+                    log_id: log.id,
+                    event_type: log.event_type.clone(),
+                    severity: log.severity.clone(),
+                    status: "submitted".to_string(),
+                    tx_signature: Some(signature.clone()),
+                });
+            }
+        }
+        Err(e) => {
+            log::error!("Failed to anchor batch {}: {}", batch.id, e);
+            db.fail_batch(batch.id).await?;
+        }
+    }
+
+    Ok(claimed.len())
+}
@@ -0,0 +1,99 @@
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::Duration;
+
+use solana_sdk::signature::Signature;
+use solana_transaction_status::TransactionConfirmationStatus;
+use tokio::sync::broadcast;
+
+use crate::db::Database;
+use crate::models::LogStatusEvent;
+use crate::backend::LogBackend;
+
+/// How often the poller checks in-flight signatures.
+const POLL_INTERVAL: Duration = Duration::from_secs(8);
+
+/// `getSignatureStatuses` accepts up to 256 signatures per call.
+const STATUS_BATCH: usize = 256;
+
+/// How many distinct signatures to pull per scan.
+const SCAN_LIMIT: i64 = 4096;
+
+/// Spawn the confirmation poller.
+///
+/// Collects signatures of all non-finalized logs, queries them in batches via
+/// [`SolanaClient::get_signature_statuses_with_history`], and advances each
+/// batch's `blockchain_status` based on its commitment level. History search is
+/// used so a signature that has merely aged out of the status cache (common for
+/// an already-confirmed tx) is not mistaken for a drop; only genuinely unknown
+/// signatures are flagged `failed` for the retry worker to re-anchor.
+pub fn spawn(db: Arc<Database>, solana: Arc<dyn LogBackend>, events: broadcast::Sender<LogStatusEvent>) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(POLL_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = poll_once(&db, &solana, &events).await {
+                log::error!("Confirmation poll failed: {}", e);
+            }
+        }
+    });
+}
+
+async fn poll_once(
+    db: &Database,
+    solana: &dyn LogBackend,
+    events: &broadcast::Sender<LogStatusEvent>,
+) -> anyhow::Result<()> {
+    let signatures = db.get_unconfirmed_signatures(SCAN_LIMIT).await?;
+    if signatures.is_empty() {
+        return Ok(());
+    }
+
+    for chunk in signatures.chunks(STATUS_BATCH) {
+        // Keep the original string alongside the parsed signature so results can
+        // be mapped back to the batch they belong to.
+        let parsed: Vec<(String, Signature)> = chunk
+            .iter()
+            .filter_map(|s| Signature::from_str(s).ok().map(|sig| (s.clone(), sig)))
+            .collect();
+        let sigs: Vec<Signature> = parsed.iter().map(|(_, sig)| *sig).collect();
+
+        let statuses = solana.get_signature_statuses_with_history(&sigs).await?;
+        for ((sig_str, _), status) in parsed.iter().zip(statuses) {
+            let new_status = match status {
+                // Unknown to the cluster even with history search: truly dropped or
+                // expired before landing, so re-anchor via the retry worker.
+                None => Some("failed"),
+                // Known to the cluster. A reported commitment level advances the
+                // status; `processed` or a not-yet-reported level means it has
+                // landed and is still climbing, so leave it `submitted`.
+                Some(s) => match s.confirmation_status {
+                    Some(TransactionConfirmationStatus::Finalized) => Some("finalized"),
+                    Some(TransactionConfirmationStatus::Confirmed) => Some("confirmed"),
+                    Some(TransactionConfirmationStatus::Processed) | None => None,
+                },
+            };
+
+            if let Some(new_status) = new_status {
+                let changed = db.update_status_by_signature(sig_str, new_status).await?;
+                if changed > 0 {
+                    if new_status == "failed" {
+                        log::warn!("Signature {} dropped; flagged {} logs for re-anchoring", sig_str, changed);
+                    }
+                    // Emit a per-log transition so live subscribers see the change.
+                    for log in db.get_logs_by_signature(sig_str).await? {
+                        let _ = events.send(LogStatusEvent {
+                            log_id: log.id,
+                            event_type: log.event_type,
+                            severity: log.severity,
+                            status: new_status.to_string(),
+                            tx_signature: Some(sig_str.clone()),
+                        });
+                    }
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
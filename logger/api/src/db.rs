@@ -1,7 +1,8 @@
 use anyhow::{Context, Result};
 use sqlx::{PgPool, postgres::PgPoolOptions};
 use sqlx::types::{Uuid, Json};
-use crate::models::{LogEntry, CreateLogRequest, LogQueryParams};
+use chrono::{DateTime, Utc};
+use crate::models::{LogEntry, LogBatch, CreateLogRequest, LogQueryParams, MerkleProof};
 
 /// Database connection pool manager
 pub struct Database {
@@ -38,11 +39,12 @@ impl Database {
         &self,
         request: &CreateLogRequest,
         hash: &str,
+        pubkey: Option<&str>,
     ) -> Result<LogEntry> {
         let log = sqlx::query_as::<_, LogEntry>(
             r#"
-            INSERT INTO logs (event_type, severity, data, hash, blockchain_status)
-            VALUES ($1, $2, $3, $4, 'pending')
+            INSERT INTO logs (event_type, severity, data, hash, blockchain_status, pubkey)
+            VALUES ($1, $2, $3, $4, 'pending', $5)
             RETURNING *
             "#,
         )
@@ -50,6 +52,7 @@ impl Database {
         .bind(&request.severity)
         .bind(Json(&request.data))
         .bind(hash)
+        .bind(pubkey)
         .fetch_one(&self.pool)
         .await
         .context("Failed to insert log entry")?;
@@ -57,30 +60,380 @@ impl Database {
         Ok(log)
     }
 
-    /// Update log entry with blockchain transaction signature
-    pub async fn update_log_tx_signature(
+    /// Fetch up to `limit` logs still awaiting blockchain anchoring.
+    ///
+    /// Returned oldest-first so a batch preserves insertion order, which the
+    /// Merkle leaf layout relies on.
+    pub async fn get_pending_logs(&self, limit: i64) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs
+            WHERE blockchain_status = 'pending'
+            ORDER BY created_at ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch pending logs")?;
+
+        Ok(logs)
+    }
+
+    /// Logs stuck in `pending` for longer than `older_than_secs`.
+    ///
+    /// These never made it onto the queue (or their worker task died), so the
+    /// reconciler re-submits them directly.
+    pub async fn get_stuck_pending_logs(&self, older_than_secs: i64, limit: i64) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs
+            WHERE blockchain_status = 'pending'
+              AND created_at < NOW() - INTERVAL '1 second' * $1
+            ORDER BY created_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(older_than_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch stuck pending logs")?;
+
+        Ok(logs)
+    }
+
+    /// Logs `submitted` longer ago than `older_than_secs` and not yet confirmed.
+    ///
+    /// Past the blockhash-expiry window an unconfirmed signature is almost
+    /// certainly dropped, so the reconciler re-checks and re-anchors these.
+    pub async fn get_stale_submitted_logs(&self, older_than_secs: i64, limit: i64) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs
+            WHERE blockchain_status = 'submitted'
+              AND updated_at < NOW() - INTERVAL '1 second' * $1
+            ORDER BY updated_at ASC
+            LIMIT $2
+            "#,
+        )
+        .bind(older_than_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch stale submitted logs")?;
+
+        Ok(logs)
+    }
+
+    /// Logs marked `confirmed` (not yet `finalized`) to re-check against chain.
+    ///
+    /// A confirmed transaction can still be rolled back by a reorg or vanish
+    /// past expiry; the reconciler re-verifies these signatures and re-anchors
+    /// any that no longer resolve on-chain.
+    pub async fn get_confirmed_logs_to_recheck(&self, limit: i64) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs
+            WHERE blockchain_status = 'confirmed'
+              AND tx_signature IS NOT NULL
+            ORDER BY updated_at ASC
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch confirmed logs to recheck")?;
+
+        Ok(logs)
+    }
+
+    /// Distinct transaction signatures of anchored-but-not-finalized logs.
+    ///
+    /// Because a whole batch shares one signature, polling these covers every
+    /// log in the batch with a single `getSignatureStatuses` entry. This
+    /// supersedes the originally-specified per-log `get_unconfirmed_logs`: dedup
+    /// by signature is what lets a handful of RPC calls confirm thousands of logs,
+    /// so callers poll signatures and fan the result back out with
+    /// [`update_status_by_signature`].
+    pub async fn get_unconfirmed_signatures(&self, limit: i64) -> Result<Vec<String>> {
+        let signatures = sqlx::query_scalar::<_, String>(
+            r#"
+            SELECT DISTINCT tx_signature FROM logs
+            WHERE tx_signature IS NOT NULL
+              AND blockchain_status IN ('submitted', 'confirmed')
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch unconfirmed signatures")?;
+
+        Ok(signatures)
+    }
+
+    /// Advance every log anchored by `tx_signature` to a new status.
+    ///
+    /// One statement upgrades an entire batch, so a handful of RPC calls can
+    /// finalize thousands of logs.
+    pub async fn update_status_by_signature(&self, tx_signature: &str, status: &str) -> Result<u64> {
+        let result = sqlx::query(
+            r#"
+            UPDATE logs
+            SET blockchain_status = $1, updated_at = NOW()
+            WHERE tx_signature = $2 AND blockchain_status <> $1
+            "#,
+        )
+        .bind(status)
+        .bind(tx_signature)
+        .execute(&self.pool)
+        .await
+        .context("Failed to bulk-update log status")?;
+
+        Ok(result.rows_affected())
+    }
+
+    /// Fetch up to `limit` logs that failed to anchor and are due for retry.
+    ///
+    /// A log is due when its `next_retry_at` has elapsed (or was never set).
+    /// Ordered oldest-deadline-first so the most overdue logs drain first.
+    pub async fn get_retryable_logs(&self, limit: i64) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs
+            WHERE blockchain_status = 'failed'
+              AND (next_retry_at IS NULL OR next_retry_at <= NOW())
+            ORDER BY next_retry_at ASC NULLS FIRST
+            LIMIT $1
+            "#,
+        )
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch retryable logs")?;
+
+        Ok(logs)
+    }
+
+    /// Record a failed retry attempt and schedule the next one.
+    ///
+    /// Bumps `attempt_count` and stores `next_retry_at` so a restart resumes the
+    /// backoff schedule rather than hammering the RPC immediately.
+    pub async fn schedule_log_retry(
         &self,
         log_id: Uuid,
-        tx_signature: &str,
-        status: &str,
+        attempt_count: i32,
+        next_retry_at: DateTime<Utc>,
     ) -> Result<()> {
         sqlx::query(
             r#"
             UPDATE logs
-            SET tx_signature = $1, blockchain_status = $2, updated_at = NOW()
+            SET attempt_count = $1, next_retry_at = $2, updated_at = NOW()
             WHERE id = $3
             "#,
         )
-        .bind(tx_signature)
-        .bind(status)
+        .bind(attempt_count)
+        .bind(next_retry_at)
         .bind(log_id)
         .execute(&self.pool)
         .await
-        .context("Failed to update log transaction signature")?;
+        .context("Failed to schedule log retry")?;
 
         Ok(())
     }
 
+    /// Atomically claim the anchorable logs among `log_ids` and open a batch over them.
+    ///
+    /// Only logs still `pending` or `failed` are claimed: the guarded
+    /// `UPDATE ... RETURNING id` transitions exactly those rows to `batching`, so a
+    /// log selected by two producers — the batcher, a queue worker and the
+    /// reconciler all scan those states — is claimed by whichever transaction
+    /// commits first and skipped by the rest. Returns the batch together with the
+    /// ids that actually transitioned, in request order; callers build proofs and
+    /// finalize only for those. Returns `None` when nothing was claimable, so no
+    /// orphan batch row is left behind.
+    pub async fn create_batch(
+        &self,
+        merkle_root: &str,
+        log_ids: &[Uuid],
+    ) -> Result<Option<(LogBatch, Vec<Uuid>)>> {
+        let mut tx = self.pool.begin().await.context("Failed to begin batch transaction")?;
+
+        // Claim first so the batch row's `log_count` reflects only the rows this
+        // producer actually won; a row already moved on by a rival is left alone.
+        let claimed = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            UPDATE logs
+            SET blockchain_status = 'batching', updated_at = NOW()
+            WHERE id = ANY($1) AND blockchain_status IN ('pending', 'failed')
+            RETURNING id
+            "#,
+        )
+        .bind(log_ids)
+        .fetch_all(&mut *tx)
+        .await
+        .context("Failed to claim logs for batch")?;
+
+        if claimed.is_empty() {
+            tx.rollback().await.context("Failed to roll back empty batch")?;
+            return Ok(None);
+        }
+
+        let batch = sqlx::query_as::<_, LogBatch>(
+            r#"
+            INSERT INTO log_batches (merkle_root, status, log_count)
+            VALUES ($1, 'pending', $2)
+            RETURNING *
+            "#,
+        )
+        .bind(merkle_root)
+        .bind(claimed.len() as i32)
+        .fetch_one(&mut *tx)
+        .await
+        .context("Failed to insert batch")?;
+
+        sqlx::query(
+            r#"
+            UPDATE logs
+            SET batch_id = $1, updated_at = NOW()
+            WHERE id = ANY($2)
+            "#,
+        )
+        .bind(batch.id)
+        .bind(&claimed)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to attach logs to batch")?;
+
+        tx.commit().await.context("Failed to commit batch transaction")?;
+
+        // Preserve the caller's ordering so leaf indices still line up with proofs.
+        let claimed_set: std::collections::HashSet<Uuid> = claimed.into_iter().collect();
+        let ordered: Vec<Uuid> = log_ids.iter().copied().filter(|id| claimed_set.contains(id)).collect();
+
+        Ok(Some((batch, ordered)))
+    }
+
+    /// Distinct ids of batches whose logs have sat in `batching` too long.
+    ///
+    /// A crash between [`create_batch`] and [`finalize_batch`]/[`fail_batch`]
+    /// strands logs in `batching`, a status no other scan selects. These batch
+    /// ids are handed to [`fail_batch`] so their logs fall back into retry.
+    pub async fn get_stuck_batching_batches(&self, older_than_secs: i64, limit: i64) -> Result<Vec<Uuid>> {
+        let ids = sqlx::query_scalar::<_, Uuid>(
+            r#"
+            SELECT DISTINCT batch_id FROM logs
+            WHERE blockchain_status = 'batching'
+              AND batch_id IS NOT NULL
+              AND updated_at < NOW() - INTERVAL '1 second' * $1
+            LIMIT $2
+            "#,
+        )
+        .bind(older_than_secs)
+        .bind(limit)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch stuck batching batches")?;
+
+        Ok(ids)
+    }
+
+    /// Seal a successfully anchored batch and stamp every log with its proof.
+    pub async fn finalize_batch(
+        &self,
+        batch_id: Uuid,
+        tx_signature: &str,
+        proofs: &[(Uuid, MerkleProof)],
+    ) -> Result<()> {
+        let mut tx = self.pool.begin().await.context("Failed to begin finalize transaction")?;
+
+        sqlx::query(
+            r#"
+            UPDATE log_batches
+            SET tx_signature = $1, status = 'submitted'
+            WHERE id = $2
+            "#,
+        )
+        .bind(tx_signature)
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to update batch signature")?;
+
+        for (log_id, proof) in proofs {
+            sqlx::query(
+                r#"
+                UPDATE logs
+                SET tx_signature = $1,
+                    blockchain_status = 'submitted',
+                    leaf_index = $2,
+                    merkle_proof = $3,
+                    updated_at = NOW()
+                WHERE id = $4
+                "#,
+            )
+            .bind(tx_signature)
+            .bind(proof.leaf_index as i32)
+            .bind(Json(serde_json::to_value(proof).unwrap_or_default()))
+            .bind(log_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to update log with Merkle proof")?;
+        }
+
+        tx.commit().await.context("Failed to commit finalize transaction")?;
+
+        Ok(())
+    }
+
+    /// Mark a batch (and its logs) as failed so they can be retried.
+    pub async fn fail_batch(&self, batch_id: Uuid) -> Result<()> {
+        let mut tx = self.pool.begin().await.context("Failed to begin fail transaction")?;
+
+        sqlx::query("UPDATE log_batches SET status = 'failed' WHERE id = $1")
+            .bind(batch_id)
+            .execute(&mut *tx)
+            .await
+            .context("Failed to mark batch failed")?;
+
+        sqlx::query(
+            r#"
+            UPDATE logs
+            SET blockchain_status = 'failed', updated_at = NOW()
+            WHERE batch_id = $1
+            "#,
+        )
+        .bind(batch_id)
+        .execute(&mut *tx)
+        .await
+        .context("Failed to mark batched logs failed")?;
+
+        tx.commit().await.context("Failed to commit fail transaction")?;
+
+        Ok(())
+    }
+
+    /// Fetch every log anchored by a given transaction signature.
+    ///
+    /// Used by the confirmation poller to emit per-log status events after a
+    /// batch's commitment level advances.
+    pub async fn get_logs_by_signature(&self, tx_signature: &str) -> Result<Vec<LogEntry>> {
+        let logs = sqlx::query_as::<_, LogEntry>(
+            r#"
+            SELECT * FROM logs WHERE tx_signature = $1
+            "#,
+        )
+        .bind(tx_signature)
+        .fetch_all(&self.pool)
+        .await
+        .context("Failed to fetch logs by signature")?;
+
+        Ok(logs)
+    }
+
     /// Get log entry by ID
     pub async fn get_log_by_id(&self, log_id: Uuid) -> Result<Option<LogEntry>> {
         let log = sqlx::query_as::<_, LogEntry>(
@@ -119,43 +472,59 @@ impl Database {
             "#,
         );
 
-        // Add filters
+        // Add filters, numbering placeholders sequentially as clauses are added.
+        let mut n = 0;
         if params.event_type.is_some() {
-            query.push_str(" AND event_type = $1");
-            count_query.push_str(" AND event_type = $1");
+            n += 1;
+            query.push_str(&format!(" AND event_type = ${}", n));
+            count_query.push_str(&format!(" AND event_type = ${}", n));
         }
         if params.severity.is_some() {
-            let param_num = if params.event_type.is_some() { 2 } else { 1 };
-            query.push_str(&format!(" AND severity = ${}", param_num));
-            count_query.push_str(&format!(" AND severity = ${}", param_num));
+            n += 1;
+            query.push_str(&format!(" AND severity = ${}", n));
+            count_query.push_str(&format!(" AND severity = ${}", n));
+        }
+        if params.pubkey.is_some() {
+            n += 1;
+            query.push_str(&format!(" AND pubkey = ${}", n));
+            count_query.push_str(&format!(" AND pubkey = ${}", n));
         }
         if params.from_date.is_some() {
-            let param_num = 1 + params.event_type.is_some() as i32 + params.severity.is_some() as i32;
-            query.push_str(&format!(" AND created_at >= ${}", param_num));
-            count_query.push_str(&format!(" AND created_at >= ${}", param_num));
+            n += 1;
+            query.push_str(&format!(" AND created_at >= ${}", n));
+            count_query.push_str(&format!(" AND created_at >= ${}", n));
         }
         if params.to_date.is_some() {
-            let param_num = 1 + params.event_type.is_some() as i32 
-                            + params.severity.is_some() as i32 
-                            + params.from_date.is_some() as i32;
-            query.push_str(&format!(" AND created_at <= ${}", param_num));
-            count_query.push_str(&format!(" AND created_at <= ${}", param_num));
+            n += 1;
+            query.push_str(&format!(" AND created_at <= ${}", n));
+            count_query.push_str(&format!(" AND created_at <= ${}", n));
+        }
+        // JSONB filters: `data->>$k = $v` (exact) or `data->>$k LIKE $v` with `%`
+        // appended to the bound value (prefix).
+        // The key is bound too so user input never lands in the SQL text.
+        for m in &params.data_match {
+            let key_param = n + 1;
+            let val_param = n + 2;
+            n += 2;
+            let clause = if m.prefix {
+                format!(" AND data->>${} LIKE ${}", key_param, val_param)
+            } else {
+                format!(" AND data->>${} = ${}", key_param, val_param)
+            };
+            query.push_str(&clause);
+            count_query.push_str(&clause);
         }
 
         query.push_str(" ORDER BY created_at DESC");
-        
-        // Add pagination params
-        let limit_param = 1 + params.event_type.is_some() as i32 
-                           + params.severity.is_some() as i32 
-                           + params.from_date.is_some() as i32 
-                           + params.to_date.is_some() as i32;
-        query.push_str(&format!(" LIMIT ${} OFFSET ${}", limit_param, limit_param + 1));
+
+        // Pagination placeholders follow every filter placeholder.
+        query.push_str(&format!(" LIMIT ${} OFFSET ${}", n + 1, n + 2));
 
         // Execute queries
         let mut logs_query = sqlx::query_as::<_, LogEntry>(&query);
         let mut count_query_exec = sqlx::query_scalar::<_, i64>(&count_query);
 
-        // Bind parameters in order
+        // Bind parameters in the same order the placeholders were numbered.
         if let Some(ref event_type) = params.event_type {
             logs_query = logs_query.bind(event_type);
             count_query_exec = count_query_exec.bind(event_type);
@@ -164,6 +533,10 @@ impl Database {
             logs_query = logs_query.bind(severity);
             count_query_exec = count_query_exec.bind(severity);
         }
+        if let Some(ref pubkey) = params.pubkey {
+            logs_query = logs_query.bind(pubkey);
+            count_query_exec = count_query_exec.bind(pubkey);
+        }
         if let Some(ref from_date) = params.from_date {
             logs_query = logs_query.bind(from_date);
             count_query_exec = count_query_exec.bind(from_date);
@@ -172,7 +545,16 @@ impl Database {
             logs_query = logs_query.bind(to_date);
             count_query_exec = count_query_exec.bind(to_date);
         }
-        
+        for m in &params.data_match {
+            let value = if m.prefix {
+                format!("{}%", m.value)
+            } else {
+                m.value.clone()
+            };
+            logs_query = logs_query.bind(m.key.clone()).bind(value.clone());
+            count_query_exec = count_query_exec.bind(m.key.clone()).bind(value);
+        }
+
         logs_query = logs_query.bind(limit).bind(offset);
 
         let logs = logs_query.fetch_all(&self.pool).await
@@ -1,16 +1,162 @@
-use actix_web::{web, HttpResponse, Responder};
+use actix_web::{web, HttpRequest, HttpResponse, Responder};
 use validator::Validate;
 use std::sync::Arc;
+use tokio::sync::broadcast;
 use log::{info, error};
 
 use crate::models::*;
 use crate::db::Database;
-use crate::solana::SolanaClient;
+use crate::backend::LogBackend;
+use crate::queue::SubmissionQueue;
+use crate::reconcile::ReconcileStats;
 
 /// Application state shared across handlers
 pub struct AppState {
-    pub db: Database,
-    pub solana: Arc<SolanaClient>,
+    pub db: Arc<Database>,
+    pub solana: Arc<dyn LogBackend>,
+    /// Broadcast channel of log status transitions, fed by the background workers.
+    pub events: broadcast::Sender<LogStatusEvent>,
+    /// Bounded submission queue for newly created logs.
+    pub queue: SubmissionQueue,
+    /// Count of anchors resubmitted by the reconciliation worker.
+    pub reconcile_stats: Arc<ReconcileStats>,
+}
+
+/// Query parameters for the verify endpoint.
+#[derive(Debug, serde::Deserialize)]
+pub struct VerifyParams {
+    /// When true, decode and return the full on-chain transaction.
+    pub verbose: Option<bool>,
+}
+
+/// Filter for the live status subscription.
+///
+/// With no fields set a client receives every transition; `log_id` pins a single
+/// log, while `event_type`/`severity` mirror the `query_logs` filters.
+#[derive(Debug, serde::Deserialize)]
+pub struct SubscribeParams {
+    pub log_id: Option<sqlx::types::Uuid>,
+    pub event_type: Option<String>,
+    pub severity: Option<String>,
+}
+
+impl SubscribeParams {
+    fn matches(&self, event: &LogStatusEvent) -> bool {
+        if let Some(id) = self.log_id {
+            if event.log_id != id {
+                return false;
+            }
+        }
+        if let Some(ref et) = self.event_type {
+            if &event.event_type != et {
+                return false;
+            }
+        }
+        if let Some(ref sev) = self.severity {
+            if &event.severity != sev {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Filter for the Server-Sent Events stream.
+///
+/// Mirrors the `query_logs` filters so a subscriber only receives events for a
+/// given `event_type`/`severity`; both absent means every event.
+#[derive(Debug, serde::Deserialize)]
+pub struct StreamParams {
+    pub event_type: Option<String>,
+    pub severity: Option<String>,
+}
+
+impl StreamParams {
+    fn matches(&self, event: &LogStatusEvent) -> bool {
+        if let Some(ref et) = self.event_type {
+            if &event.event_type != et {
+                return false;
+            }
+        }
+        if let Some(ref sev) = self.severity {
+            if &event.severity != sev {
+                return false;
+            }
+        }
+        true
+    }
+}
+
+/// Stream live log and confirmation updates via Server-Sent Events
+/// GET /api/v1/logs/stream?event_type=...&severity=...
+pub async fn stream(
+    state: web::Data<AppState>,
+    params: web::Query<StreamParams>,
+) -> impl Responder {
+    use tokio_stream::wrappers::BroadcastStream;
+    use tokio_stream::StreamExt;
+
+    let rx = state.events.subscribe();
+    let filter = params.into_inner();
+
+    let stream = BroadcastStream::new(rx).filter_map(move |res| {
+        let event = res.ok()?;
+        if !filter.matches(&event) {
+            return None;
+        }
+        let data = actix_web_lab::sse::Data::new_json(&event).ok()?.event("log_status");
+        Some(Ok::<_, std::convert::Infallible>(actix_web_lab::sse::Event::Data(data)))
+    });
+
+    actix_web_lab::sse::Sse::from_stream(stream)
+        .with_keep_alive(std::time::Duration::from_secs(15))
+}
+
+/// Subscribe to live log-status updates over WebSocket
+/// GET /api/v1/logs/subscribe?log_id=...|event_type=...&severity=...
+pub async fn subscribe(
+    req: HttpRequest,
+    body: web::Payload,
+    state: web::Data<AppState>,
+    params: web::Query<SubscribeParams>,
+) -> Result<HttpResponse, actix_web::Error> {
+    let (response, mut session, mut msg_stream) = actix_ws::handle(&req, body)?;
+    let mut rx = state.events.subscribe();
+    let filter = params.into_inner();
+
+    actix_web::rt::spawn(async move {
+        loop {
+            tokio::select! {
+                event = rx.recv() => {
+                    match event {
+                        Ok(event) if filter.matches(&event) => {
+                            if let Ok(json) = serde_json::to_string(&event) {
+                                if session.text(json).await.is_err() {
+                                    break;
+                                }
+                            }
+                        }
+                        Ok(_) => {}
+                        // Lagged subscribers skip missed events; a closed channel ends the stream.
+                        Err(broadcast::error::RecvError::Lagged(_)) => {}
+                        Err(broadcast::error::RecvError::Closed) => break,
+                    }
+                }
+                incoming = msg_stream.recv() => {
+                    match incoming {
+                        Some(Ok(actix_ws::Message::Ping(bytes))) => {
+                            let _ = session.pong(&bytes).await;
+                        }
+                        Some(Ok(actix_ws::Message::Close(_))) | None => break,
+                        _ => {}
+                    }
+                }
+            }
+        }
+        let _ = session.close(None).await;
+    });
+
+    Ok(response)
 }
 
 /// Health check endpoint
@@ -47,12 +193,23 @@ pub async fn create_log(
         });
     }
 
+    // Verify the client's Ed25519 signature, if one was supplied
+    let verified_pubkey = match request.verify_signature() {
+        Ok(pubkey) => pubkey,
+        Err(msg) => {
+            return HttpResponse::BadRequest().json(ErrorResponse {
+                error: "signature_error".to_string(),
+                message: msg,
+            });
+        }
+    };
+
     // Compute hash
     let hash = request.compute_hash();
     info!("Creating log entry with hash: {}", hash);
 
     // Insert into database
-    let log_entry = match state.db.insert_log(&request, &hash).await {
+    let log_entry = match state.db.insert_log(&request, &hash, verified_pubkey.as_deref()).await {
         Ok(entry) => entry,
         Err(e) => {
             error!("Failed to insert log: {}", e);
@@ -63,37 +220,35 @@ pub async fn create_log(
         }
     };
 
-    // Submit to blockchain (async, non-blocking)
-    let log_id = log_entry.id;
-    let hash_clone = hash.clone();
-    let db_clone = state.db.pool().clone();
-    let solana_clone = Arc::clone(&state.solana);
-
-    // Spawn background task for blockchain submission
-    tokio::spawn(async move {
-        match solana_clone.submit_log_hash(&hash_clone).await {
-            Ok(signature) => {
-                info!("Log {} submitted to blockchain: {}", log_id, signature);
-                
-                // Update database with transaction signature
-                let db = Database { pool: db_clone };
-                if let Err(e) = db.update_log_tx_signature(log_id, &signature, "confirmed").await {
-                    error!("Failed to update log tx signature: {}", e);
-                }
-            }
-            Err(e) => {
-                error!("Failed to submit log to blockchain: {}", e);
-                
-                // Update status to failed
-                let db = Database { pool: db_clone };
-                if let Err(e) = db.update_log_tx_signature(log_id, "", "failed").await {
-                    error!("Failed to update log status: {}", e);
-                }
-            }
-        }
+    // Hand the log to the bounded submission queue for the fast path. The row is
+    // already persisted as `pending`, so a full queue is not a rejection: we
+    // fall back to 202 Accepted and let the batcher anchor it on its next flush
+    // rather than 503-ing a write that actually succeeded (which would make a
+    // retrying client create duplicate logs). A `pending` row the batcher picks up
+    // can only be anchored once — `create_batch` claims it atomically — so the
+    // queue worker and the batcher never double-anchor the same log.
+    let queued = state.queue.try_enqueue(log_entry.id).is_ok();
+    if queued {
+        info!("Log {} queued for anchoring", log_entry.id);
+    } else {
+        info!("Submission queue full; log {} left for the batcher", log_entry.id);
+    }
+
+    // Publish the creation event so live streams see the log enter the pipeline.
+    let _ = state.events.send(LogStatusEvent {
+        log_id: log_entry.id,
+        event_type: log_entry.event_type.clone(),
+        severity: log_entry.severity.clone(),
+        status: log_entry.blockchain_status.clone(),
+        tx_signature: None,
     });
 
-    HttpResponse::Created().json(CreateLogResponse::from(log_entry))
+    let body = CreateLogResponse::from(log_entry);
+    if queued {
+        HttpResponse::Created().json(body)
+    } else {
+        HttpResponse::Accepted().json(body)
+    }
 }
 
 /// Get log by ID
@@ -131,9 +286,22 @@ pub async fn get_log(
 /// Query logs with filters
 /// GET /api/v1/logs?event_type=...&severity=...&limit=...&offset=...
 pub async fn query_logs(
+    req: HttpRequest,
     state: web::Data<AppState>,
     params: web::Query<LogQueryParams>,
 ) -> impl Responder {
+    let mut params = params.into_inner();
+
+    // `data_match` can appear multiple times, which the struct deserializer
+    // can't collect into a Vec, so re-read the raw query string as pairs.
+    let pairs: Vec<(String, String)> =
+        serde_urlencoded::from_str(req.query_string()).unwrap_or_default();
+    params.data_match = pairs
+        .into_iter()
+        .filter(|(k, _)| k == "data_match")
+        .filter_map(|(_, v)| DataMatch::parse(&v))
+        .collect();
+
     match state.db.query_logs(&params).await {
         Ok((logs, total)) => {
             let limit = params.limit.unwrap_or(100);
@@ -163,7 +331,9 @@ pub async fn query_logs(
 pub async fn verify_log(
     state: web::Data<AppState>,
     path: web::Path<String>,
+    params: web::Query<VerifyParams>,
 ) -> impl Responder {
+    let verbose = params.verbose.unwrap_or(false);
     let log_id = match path.parse() {
         Ok(id) => id,
         Err(_) => {
@@ -192,7 +362,7 @@ pub async fn verify_log(
         }
     };
 
-    // If no transaction signature, log hasn't been submitted yet
+    // If no transaction signature, the log's batch hasn't been anchored yet
     let tx_signature = match &log.tx_signature {
         Some(sig) => sig,
         None => {
@@ -203,27 +373,85 @@ pub async fn verify_log(
                 blockchain_hash: None,
                 tx_signature: None,
                 blockchain_status: log.blockchain_status,
-                message: "Log not yet submitted to blockchain".to_string(),
+                message: "Log not yet anchored to blockchain".to_string(),
+                merkle_proof: None,
+                transaction_detail: None,
             });
         }
     };
 
-    // Verify hash on blockchain
-    match state.solana.verify_log_hash(tx_signature).await {
-        Ok(Some(blockchain_hash)) => {
-            let is_valid = blockchain_hash == log.hash;
+    // Decode the stored inclusion proof and local leaf hash.
+    let proof: MerkleProof = match log.merkle_proof.as_ref().and_then(|p| serde_json::from_value(p.0.clone()).ok()) {
+        Some(proof) => proof,
+        None => {
+            return HttpResponse::Ok().json(VerificationResponse {
+                log_id: log.id,
+                is_valid: false,
+                local_hash: log.hash,
+                blockchain_hash: None,
+                tx_signature: Some(tx_signature.clone()),
+                blockchain_status: log.blockchain_status,
+                message: "Merkle proof missing for log".to_string(),
+                merkle_proof: None,
+                transaction_detail: None,
+            });
+        }
+    };
+
+    let leaf = match decode_hash(&log.hash) {
+        Ok(leaf) => leaf,
+        Err(e) => {
+            error!("Stored hash is not valid hex: {}", e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "hash_error".to_string(),
+                message: "Stored log hash is malformed".to_string(),
+            });
+        }
+    };
+
+    // Recompute the batch root from the leaf and proof, then compare it to the
+    // root decoded from the on-chain anchoring transaction.
+    let computed_root = match merkle_root_from_proof(leaf, &proof) {
+        Ok(root) => hex::encode(root),
+        Err(e) => {
+            error!("Malformed Merkle proof for log {}: {}", log.id, e);
+            return HttpResponse::InternalServerError().json(ErrorResponse {
+                error: "proof_error".to_string(),
+                message: "Stored Merkle proof is malformed".to_string(),
+            });
+        }
+    };
+
+    // In verbose mode, decode the full anchoring transaction for the audit trail.
+    let transaction_detail = if verbose {
+        match state.solana.get_transaction_detail(tx_signature).await {
+            Ok(detail) => detail,
+            Err(e) => {
+                error!("Failed to decode transaction {}: {}", tx_signature, e);
+                None
+            }
+        }
+    } else {
+        None
+    };
+
+    match state.solana.get_log_root(tx_signature).await {
+        Ok(Some(onchain_root)) => {
+            let is_valid = onchain_root == computed_root;
             HttpResponse::Ok().json(VerificationResponse {
                 log_id: log.id,
                 is_valid,
                 local_hash: log.hash.clone(),
-                blockchain_hash: Some(blockchain_hash),
+                blockchain_hash: Some(onchain_root),
                 tx_signature: Some(tx_signature.clone()),
                 blockchain_status: log.blockchain_status,
                 message: if is_valid {
                     "Log verified successfully".to_string()
                 } else {
-                    "Hash mismatch - data may be corrupted".to_string()
+                    "Merkle root mismatch - data may be corrupted".to_string()
                 },
+                merkle_proof: Some(proof),
+                transaction_detail,
             })
         }
         Ok(None) => {
@@ -234,7 +462,9 @@ pub async fn verify_log(
                 blockchain_hash: None,
                 tx_signature: Some(tx_signature.clone()),
                 blockchain_status: log.blockchain_status,
-                message: "Hash not found in blockchain transaction".to_string(),
+                message: "Merkle root not found in blockchain transaction".to_string(),
+                merkle_proof: Some(proof),
+                transaction_detail,
             })
         }
         Err(e) => {
@@ -256,6 +486,10 @@ pub async fn get_stats(state: web::Data<AppState>) -> impl Responder {
         pending_logs: i64,
         confirmed_logs: i64,
         failed_logs: i64,
+        queued: i64,
+        in_flight: i64,
+        submitted_this_session: u64,
+        reconciled: u64,
         wallet_pubkey: String,
         wallet_balance_lamports: Option<u64>,
     }
@@ -289,11 +523,16 @@ pub async fn get_stats(state: web::Data<AppState>) -> impl Responder {
 
     let wallet_balance = state.solana.get_balance().ok();
 
+    let queue_stats = state.queue.stats();
     let stats = Stats {
         total_logs,
         pending_logs,
         confirmed_logs,
         failed_logs,
+        queued: queue_stats.queued.load(std::sync::atomic::Ordering::Relaxed),
+        in_flight: queue_stats.in_flight.load(std::sync::atomic::Ordering::Relaxed),
+        submitted_this_session: queue_stats.submitted_session.load(std::sync::atomic::Ordering::Relaxed),
+        reconciled: state.reconcile_stats.load(std::sync::atomic::Ordering::Relaxed),
         wallet_pubkey: state.solana.pubkey().to_string(),
         wallet_balance_lamports: wallet_balance,
     };
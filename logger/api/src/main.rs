@@ -2,6 +2,11 @@ mod models;
 mod db;
 mod solana;
 mod handlers;
+mod backend;
+mod batcher;
+mod confirm;
+mod queue;
+mod reconcile;
 
 use actix_web::{web, App, HttpServer, middleware};
 use actix_cors::Cors;
@@ -12,7 +17,10 @@ use anyhow::Result;
 
 use crate::db::Database;
 use crate::solana::SolanaClient;
+use crate::backend::{AnchorLogBackend, LogBackend, MockLogBackend};
 use crate::handlers::AppState;
+use std::str::FromStr;
+use solana_sdk::pubkey::Pubkey;
 
 #[actix_web::main]
 async fn main() -> Result<()> {
@@ -41,38 +49,72 @@ async fn main() -> Result<()> {
     log::info!("📊 Connecting to database: {}", database_url);
     
     // Initialize database
-    let db = Database::new(&database_url).await?;
-    
+    let db = Arc::new(Database::new(&database_url).await?);
+
     log::info!("✅ Database connected");
 
-    // Initialize Solana client
-    log::info!("🔗 Connecting to Solana RPC: {}", solana_rpc_url);
-    let solana = match SolanaClient::new(&solana_rpc_url, &solana_keypair_path) {
-        Ok(client) => {
-            log::info!("✅ Solana client initialized");
+    // Select the anchoring backend by configuration (memo | anchor | mock)
+    let backend_kind = env::var("LOG_BACKEND").unwrap_or_else(|_| "memo".to_string());
+    log::info!("🔗 Connecting to Solana RPC: {} (backend: {})", solana_rpc_url, backend_kind);
+
+    let solana: Arc<dyn LogBackend> = match backend_kind.as_str() {
+        "mock" => {
+            log::info!("✅ Using in-memory mock backend");
+            Arc::new(MockLogBackend::new())
+        }
+        "anchor" => {
+            let program_id = env::var("LOG_PROGRAM_ID")
+                .expect("LOG_PROGRAM_ID must be set for the anchor backend");
+            let program_id = Pubkey::from_str(&program_id)
+                .expect("Invalid LOG_PROGRAM_ID");
+            let client = AnchorLogBackend::new(&solana_rpc_url, &solana_keypair_path, program_id)?;
+            log::info!("✅ Anchor program backend initialized");
             log::info!("💰 Wallet: {}", client.pubkey());
-            if let Ok(balance) = client.get_balance() {
-                log::info!("💰 Balance: {} lamports ({} SOL)", 
-                    balance, 
-                    balance as f64 / 1_000_000_000.0
-                );
-            }
             Arc::new(client)
         }
-        Err(e) => {
-            log::error!("❌ Failed to initialize Solana client: {}", e);
-            log::warn!("⚠️  API will run without blockchain integration");
-            log::warn!("⚠️  Set SOLANA_KEYPAIR_PATH to enable blockchain features");
-            // For development, you might want to panic here
-            // or create a mock client
-            return Err(e);
-        }
+        _ => match SolanaClient::new(&solana_rpc_url, &solana_keypair_path) {
+            Ok(client) => {
+                log::info!("✅ Solana memo client initialized");
+                log::info!("💰 Wallet: {}", client.pubkey());
+                if let Ok(balance) = client.get_balance() {
+                    log::info!("💰 Balance: {} lamports ({} SOL)",
+                        balance,
+                        balance as f64 / 1_000_000_000.0
+                    );
+                }
+                Arc::new(client)
+            }
+            Err(e) => {
+                log::error!("❌ Failed to initialize Solana client: {}", e);
+                log::warn!("⚠️  API will not start without blockchain integration");
+                log::warn!("⚠️  Set SOLANA_KEYPAIR_PATH to enable blockchain features");
+                return Err(e);
+            }
+        },
     };
 
+    // Broadcast channel fanning log-status transitions out to live subscribers
+    let (events_tx, _) = tokio::sync::broadcast::channel(1024);
+
+    // Spawn the background batcher that anchors pending logs under Merkle roots
+    batcher::spawn(Arc::clone(&db), Arc::clone(&solana), events_tx.clone());
+
+    // Spawn the confirmation poller that upgrades submitted logs to confirmed/finalized
+    confirm::spawn(Arc::clone(&db), Arc::clone(&solana), events_tx.clone());
+
+    // Bounded submission queue: the ingestion fast-path for newly created logs
+    let queue = queue::spawn(Arc::clone(&db), Arc::clone(&solana), events_tx.clone());
+
+    // Spawn the reconciler that recovers stuck/dropped anchors
+    let reconcile_stats = reconcile::spawn(Arc::clone(&db), Arc::clone(&solana), events_tx.clone());
+
     // Create app state
     let app_state = web::Data::new(AppState {
         db,
         solana,
+        events: events_tx,
+        queue,
+        reconcile_stats,
     });
 
     log::info!("🌐 Starting HTTP server at {}:{}", api_host, api_port);
@@ -93,6 +135,8 @@ async fn main() -> Result<()> {
                 web::scope("/api/v1")
                     .route("/logs", web::post().to(handlers::create_log))
                     .route("/logs", web::get().to(handlers::query_logs))
+                    .route("/logs/subscribe", web::get().to(handlers::subscribe))
+                    .route("/logs/stream", web::get().to(handlers::stream))
                     .route("/logs/{id}", web::get().to(handlers::get_log))
                     .route("/logs/{id}/verify", web::get().to(handlers::verify_log))
                     .route("/stats", web::get().to(handlers::get_stats))
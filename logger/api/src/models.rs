@@ -13,6 +13,12 @@ pub struct LogEntry {
     pub hash: String,
     pub tx_signature: Option<String>,
     pub blockchain_status: String,
+    pub pubkey: Option<String>,
+    pub batch_id: Option<Uuid>,
+    pub leaf_index: Option<i32>,
+    pub merkle_proof: Option<Json<serde_json::Value>>,
+    pub attempt_count: i32,
+    pub next_retry_at: Option<DateTime<Utc>>,
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
 }
@@ -25,8 +31,14 @@ pub struct CreateLogRequest {
     
     #[validate(length(min = 1, max = 50))]
     pub severity: String,
-    
+
     pub data: serde_json::Value,
+
+    /// Optional Ed25519 public key (base58) of the signer.
+    pub pubkey: Option<String>,
+
+    /// Optional Ed25519 signature (base58) over the canonical payload.
+    pub signature: Option<String>,
 }
 
 /// Response after creating a log entry
@@ -36,6 +48,7 @@ pub struct CreateLogResponse {
     pub hash: String,
     pub tx_signature: Option<String>,
     pub blockchain_status: String,
+    pub pubkey: Option<String>,
     pub created_at: DateTime<Utc>,
 }
 
@@ -44,10 +57,49 @@ pub struct CreateLogResponse {
 pub struct LogQueryParams {
     pub event_type: Option<String>,
     pub severity: Option<String>,
+    pub pubkey: Option<String>,
     pub limit: Option<i64>,
     pub offset: Option<i64>,
     pub from_date: Option<DateTime<Utc>>,
     pub to_date: Option<DateTime<Utc>>,
+    /// Repeatable `data.<key>=<value>` filters over the JSONB `data` payload.
+    ///
+    /// A trailing `*` on the value switches from exact equality to a prefix
+    /// match, e.g. `data.user_id=42` or `data.path=/admin*`. Parsed from the
+    /// raw query string since it may appear multiple times.
+    #[serde(skip)]
+    pub data_match: Vec<DataMatch>,
+}
+
+/// A single parsed JSONB filter clause.
+#[derive(Debug, Clone)]
+pub struct DataMatch {
+    pub key: String,
+    pub value: String,
+    pub prefix: bool,
+}
+
+impl DataMatch {
+    /// Parse one `data.<key>=<value>` clause, returning `None` if malformed.
+    ///
+    /// A trailing `*` on the value marks a prefix match; the leading `data.`
+    /// namespace is optional.
+    pub fn parse(raw: &str) -> Option<Self> {
+        let (lhs, rhs) = raw.split_once('=')?;
+        let key = lhs.trim().strip_prefix("data.").unwrap_or(lhs.trim());
+        if key.is_empty() {
+            return None;
+        }
+        let (value, prefix) = match rhs.strip_suffix('*') {
+            Some(stripped) => (stripped.to_string(), true),
+            None => (rhs.to_string(), false),
+        };
+        Some(DataMatch {
+            key: key.to_string(),
+            value,
+            prefix,
+        })
+    }
 }
 
 /// Response for log verification
@@ -60,6 +112,144 @@ pub struct VerificationResponse {
     pub tx_signature: Option<String>,
     pub blockchain_status: String,
     pub message: String,
+    /// Merkle inclusion proof (present for batch-anchored logs)
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub merkle_proof: Option<MerkleProof>,
+    /// Decoded on-chain transaction, populated only in verbose mode
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transaction_detail: Option<TransactionDetail>,
+}
+
+/// A batch of log hashes anchored on-chain under a single Merkle root
+#[derive(Debug, Clone, Serialize, Deserialize, sqlx::FromRow)]
+pub struct LogBatch {
+    pub id: Uuid,
+    pub merkle_root: String,
+    pub tx_signature: Option<String>,
+    pub status: String,
+    pub log_count: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Merkle inclusion proof for a single leaf
+///
+/// `siblings` holds the ordered sibling hashes (hex) walking from the leaf up
+/// to the root; `is_left` marks, for each level, whether the sibling sits on
+/// the left of the current node (so the hash order can be reconstructed).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MerkleProof {
+    pub leaf_index: usize,
+    pub siblings: Vec<String>,
+    pub is_left: Vec<bool>,
+}
+
+/// Build a binary Merkle tree over `leaves` and return its root.
+///
+/// Leaves are the raw 32-byte log hashes, parents are `SHA256(left || right)`,
+/// and when a level has an odd number of nodes the last node is duplicated. A
+/// single-leaf tree has `root == leaf`.
+pub fn merkle_root(leaves: &[[u8; 32]]) -> [u8; 32] {
+    assert!(!leaves.is_empty(), "cannot build a Merkle tree over zero leaves");
+
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    while level.len() > 1 {
+        level = merkle_parents(&level);
+    }
+    level[0]
+}
+
+/// Generate the inclusion proof for the leaf at `index`.
+pub fn merkle_proof(leaves: &[[u8; 32]], index: usize) -> MerkleProof {
+    assert!(index < leaves.len(), "leaf index out of range");
+
+    let mut siblings = Vec::new();
+    let mut is_left = Vec::new();
+
+    let mut level: Vec<[u8; 32]> = leaves.to_vec();
+    let mut pos = index;
+    while level.len() > 1 {
+        // Duplicate the last node for odd-sized levels, mirroring `merkle_parents`.
+        let sibling_pos = if pos % 2 == 0 { pos + 1 } else { pos - 1 };
+        let sibling = level.get(sibling_pos).copied().unwrap_or(level[pos]);
+        siblings.push(hex::encode(sibling));
+        is_left.push(sibling_pos < pos);
+
+        level = merkle_parents(&level);
+        pos /= 2;
+    }
+
+    MerkleProof {
+        leaf_index: index,
+        siblings,
+        is_left,
+    }
+}
+
+/// Recompute a Merkle root from a leaf hash and its inclusion proof.
+pub fn merkle_root_from_proof(leaf: [u8; 32], proof: &MerkleProof) -> Result<[u8; 32], hex::FromHexError> {
+    let mut node = leaf;
+    for (sibling_hex, &left) in proof.siblings.iter().zip(proof.is_left.iter()) {
+        let sibling = decode_hash(sibling_hex)?;
+        node = if left {
+            hash_pair(&sibling, &node)
+        } else {
+            hash_pair(&node, &sibling)
+        };
+    }
+    Ok(node)
+}
+
+/// Decode a 64-char hex string into a 32-byte hash.
+pub fn decode_hash(hex_str: &str) -> Result<[u8; 32], hex::FromHexError> {
+    let bytes = hex::decode(hex_str)?;
+    let mut out = [0u8; 32];
+    if bytes.len() != 32 {
+        return Err(hex::FromHexError::InvalidStringLength);
+    }
+    out.copy_from_slice(&bytes);
+    Ok(out)
+}
+
+/// Collapse one Merkle level into the next, duplicating the last node when odd.
+fn merkle_parents(level: &[[u8; 32]]) -> Vec<[u8; 32]> {
+    let mut parents = Vec::with_capacity((level.len() + 1) / 2);
+    let mut i = 0;
+    while i < level.len() {
+        let left = level[i];
+        let right = if i + 1 < level.len() { level[i + 1] } else { left };
+        parents.push(hash_pair(&left, &right));
+        i += 2;
+    }
+    parents
+}
+
+/// `SHA256(left || right)` used for internal Merkle nodes.
+fn hash_pair(left: &[u8; 32], right: &[u8; 32]) -> [u8; 32] {
+    use sha2::{Sha256, Digest};
+
+    let mut hasher = Sha256::new();
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Decoded on-chain evidence for an anchoring transaction.
+///
+/// Surfaced by the verbose verification mode so auditors get the exact slot and
+/// block time — not just a boolean — for their compliance trail.
+#[derive(Debug, Clone, Serialize)]
+pub struct TransactionDetail {
+    pub slot: u64,
+    pub block_time: Option<i64>,
+    pub confirmation_status: Option<String>,
+    /// Number of confirmations reported for the transaction, when known.
+    pub confirmations: Option<usize>,
+    pub fee: Option<u64>,
+    pub signer: Option<String>,
+    /// Recent blockhash the transaction was signed against.
+    pub recent_blockhash: Option<String>,
+    /// The cleanly decoded memo/instruction payload carrying the hash or root.
+    pub memo: Option<String>,
 }
 
 /// Pagination metadata
@@ -71,6 +261,20 @@ pub struct PaginatedResponse<T> {
     pub offset: i64,
 }
 
+/// A blockchain-status transition broadcast to live subscribers.
+///
+/// Published by the submission/confirmation workers whenever a log moves
+/// between `pending` → `submitted` → `confirmed`/`finalized`/`failed`, and
+/// fanned out to WebSocket clients through an internal broadcast channel.
+#[derive(Debug, Clone, Serialize)]
+pub struct LogStatusEvent {
+    pub log_id: Uuid,
+    pub event_type: String,
+    pub severity: String,
+    pub status: String,
+    pub tx_signature: Option<String>,
+}
+
 /// Health check response
 #[derive(Debug, Serialize)]
 pub struct HealthResponse {
@@ -88,23 +292,50 @@ pub struct ErrorResponse {
 }
 
 impl CreateLogRequest {
-    /// Compute SHA-256 hash of the log data
-    pub fn compute_hash(&self) -> String {
-        use sha2::{Sha256, Digest};
-        
-        // Create a deterministic string from the log data
-        let data_str = format!(
+    /// Deterministic byte representation of the log, used for both hashing and
+    /// signature verification so a client signs exactly what gets hashed.
+    pub fn canonical_payload(&self) -> String {
+        format!(
             "{}:{}:{}",
             self.event_type,
             self.severity,
             serde_json::to_string(&self.data).unwrap_or_default()
-        );
-        
+        )
+    }
+
+    /// Compute SHA-256 hash of the log data
+    pub fn compute_hash(&self) -> String {
+        use sha2::{Sha256, Digest};
+
         let mut hasher = Sha256::new();
-        hasher.update(data_str.as_bytes());
+        hasher.update(self.canonical_payload().as_bytes());
         let result = hasher.finalize();
         hex::encode(result)
     }
+
+    /// Verify the client's Ed25519 signature over the canonical payload.
+    ///
+    /// Returns `Ok(None)` when the request is unsigned, `Ok(Some(pubkey))` when
+    /// a valid signature is present, and `Err` when the pair is malformed or the
+    /// signature does not verify.
+    pub fn verify_signature(&self) -> Result<Option<String>, String> {
+        use std::str::FromStr;
+        use solana_sdk::{pubkey::Pubkey, signature::Signature};
+
+        match (&self.pubkey, &self.signature) {
+            (None, None) => Ok(None),
+            (Some(pubkey), Some(signature)) => {
+                let pk = Pubkey::from_str(pubkey).map_err(|_| "invalid pubkey".to_string())?;
+                let sig = Signature::from_str(signature).map_err(|_| "invalid signature".to_string())?;
+                if sig.verify(pk.as_ref(), self.canonical_payload().as_bytes()) {
+                    Ok(Some(pubkey.clone()))
+                } else {
+                    Err("signature does not match payload".to_string())
+                }
+            }
+            _ => Err("pubkey and signature must be supplied together".to_string()),
+        }
+    }
 }
 
 impl From<LogEntry> for CreateLogResponse {
@@ -114,7 +345,67 @@ impl From<LogEntry> for CreateLogResponse {
             hash: entry.hash,
             tx_signature: entry.tx_signature,
             blockchain_status: entry.blockchain_status,
+            pubkey: entry.pubkey,
             created_at: entry.created_at,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Distinct, deterministic leaves: leaf `i` is all bytes `i`.
+    fn leaves(n: u8) -> Vec<[u8; 32]> {
+        (0..n).map(|i| [i; 32]).collect()
+    }
+
+    #[test]
+    fn single_leaf_root_equals_leaf() {
+        let leaf = [7u8; 32];
+        assert_eq!(merkle_root(&[leaf]), leaf);
+
+        // And its (empty) proof round-trips back to the same root.
+        let proof = merkle_proof(&[leaf], 0);
+        assert!(proof.siblings.is_empty());
+        assert_eq!(merkle_root_from_proof(leaf, &proof).unwrap(), leaf);
+    }
+
+    #[test]
+    fn proof_round_trips_for_every_leaf() {
+        // Cover odd and even counts, including the odd-leaf duplication path.
+        for n in 1..=9u8 {
+            let ls = leaves(n);
+            let root = merkle_root(&ls);
+            for (i, leaf) in ls.iter().enumerate() {
+                let proof = merkle_proof(&ls, i);
+                assert_eq!(proof.leaf_index, i);
+                assert_eq!(
+                    merkle_root_from_proof(*leaf, &proof).unwrap(),
+                    root,
+                    "proof for leaf {} of {} did not recompute the root",
+                    i, n
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn odd_level_duplicates_last_node() {
+        // Three leaves: the lone node at the leaf level pairs with itself, so
+        // a[2]'s sibling in its proof is a[2] again.
+        let ls = leaves(3);
+        let proof = merkle_proof(&ls, 2);
+        assert_eq!(proof.siblings[0], hex::encode(ls[2]));
+        assert_eq!(merkle_root_from_proof(ls[2], &proof).unwrap(), merkle_root(&ls));
+    }
+
+    #[test]
+    fn tampered_leaf_breaks_verification() {
+        let ls = leaves(4);
+        let root = merkle_root(&ls);
+        let proof = merkle_proof(&ls, 1);
+        let wrong = [0xabu8; 32];
+        assert_ne!(merkle_root_from_proof(wrong, &proof).unwrap(), root);
+    }
+}
@@ -0,0 +1,220 @@
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use sqlx::types::Uuid;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::db::Database;
+use crate::models::{self, LogStatusEvent, MerkleProof};
+use crate::backend::LogBackend;
+
+/// Default bound on in-flight submission work before backpressure kicks in.
+const DEFAULT_CAPACITY: usize = 1024;
+
+/// Default ceiling on how many queued logs a worker coalesces into one batch.
+const DEFAULT_DRAIN_MAX: usize = 256;
+
+/// Base retry backoff; doubles per attempt up to [`MAX_BACKOFF`].
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+const MAX_ATTEMPTS: u32 = 6;
+
+/// Observable counters for the submission pipeline.
+///
+/// `queued` and `in_flight` track live depth; `submitted_session` is a
+/// monotonic count of logs the queue workers anchored (reaching `submitted`)
+/// since process start. Anchors made by the batcher or the reconciler are not
+/// counted here.
+/// It deliberately counts submissions, not confirmations — the latter are
+/// advanced asynchronously by the confirmation poller.
+#[derive(Debug, Default)]
+pub struct QueueStats {
+    pub queued: AtomicI64,
+    pub in_flight: AtomicI64,
+    pub submitted_session: AtomicU64,
+}
+
+/// Handle to the bounded submission queue.
+#[derive(Clone)]
+pub struct SubmissionQueue {
+    tx: mpsc::Sender<Uuid>,
+    stats: Arc<QueueStats>,
+}
+
+impl SubmissionQueue {
+    /// Enqueue a freshly inserted log for anchoring.
+    ///
+    /// Returns `Err` at once when the queue is at capacity; it never waits for
+    /// room. `create_log` then answers 202 Accepted instead of 201, and the log
+    /// stays `pending` until the batcher safety net picks it up.
+    pub fn try_enqueue(&self, log_id: Uuid) -> Result<(), ()> {
+        match self.tx.try_send(log_id) {
+            Ok(()) => {
+                self.stats.queued.fetch_add(1, Ordering::Relaxed);
+                Ok(())
+            }
+            Err(_) => Err(()),
+        }
+    }
+
+    pub fn stats(&self) -> &QueueStats {
+        &self.stats
+    }
+}
+
+/// Spawn the submission worker pool and return a handle for enqueuing.
+///
+/// Work is fed through a bounded MPSC channel to a pool of `N` workers (derived
+/// from available parallelism). A worker blocks for one log id, then greedily
+/// drains others already waiting, up to `SUBMIT_DRAIN_MAX` ids in all, and anchors the whole
+/// burst as a single multi-leaf Merkle batch with exponential-backoff retry, so
+/// under load the "N transactions into one" amortization actually happens. The
+/// channel bound gives `create_log` a place to exert backpressure instead of
+/// unboundedly spawning tasks.
+pub fn spawn(
+    db: Arc<Database>,
+    solana: Arc<dyn LogBackend>,
+    events: broadcast::Sender<LogStatusEvent>,
+) -> SubmissionQueue {
+    let capacity = std::env::var("SUBMIT_QUEUE_CAPACITY")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_CAPACITY);
+    let drain_max = std::env::var("SUBMIT_DRAIN_MAX")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(DEFAULT_DRAIN_MAX);
+    let workers = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(4);
+
+    let (tx, rx) = mpsc::channel::<Uuid>(capacity);
+    let stats = Arc::new(QueueStats::default());
+    let rx = Arc::new(tokio::sync::Mutex::new(rx));
+
+    for _ in 0..workers {
+        let db = Arc::clone(&db);
+        let solana = Arc::clone(&solana);
+        let events = events.clone();
+        let stats = Arc::clone(&stats);
+        let rx = Arc::clone(&rx);
+        tokio::spawn(async move {
+            loop {
+                // Block for one id, then coalesce any others already queued into
+                // the same batch without blocking further.
+                let batch = {
+                    let mut guard = rx.lock().await;
+                    let Some(first) = guard.recv().await else { break };
+                    let mut batch = vec![first];
+                    while batch.len() < drain_max {
+                        match guard.try_recv() {
+                            Ok(id) => batch.push(id),
+                            Err(_) => break,
+                        }
+                    }
+                    batch
+                };
+
+                let n = batch.len() as i64;
+                stats.queued.fetch_sub(n, Ordering::Relaxed);
+                stats.in_flight.fetch_add(n, Ordering::Relaxed);
+                if let Err(e) = submit_batch(&db, &solana, &events, &stats, &batch).await {
+                    log::error!("Submission worker error for {} logs: {}", batch.len(), e);
+                }
+                stats.in_flight.fetch_sub(n, Ordering::Relaxed);
+            }
+        });
+    }
+
+    log::info!(
+        "Submission queue ready: {} workers, capacity {}, drain max {}",
+        workers, capacity, drain_max
+    );
+    SubmissionQueue { tx, stats }
+}
+
+/// Anchor a burst of queued logs as one multi-leaf Merkle batch with retry.
+///
+/// Builds a single Merkle tree over the logs' hashes and anchors only its root
+/// through the same `create_batch`/`submit_log_root`/`finalize_batch` path the
+/// batcher uses, stamping each log with the inclusion proof `verify_log`
+/// requires. Coalescing the burst is what turns N transactions into one under
+/// load; a single-id burst degenerates to a one-leaf batch (root == leaf).
+async fn submit_batch(
+    db: &Database,
+    solana: &dyn LogBackend,
+    events: &broadcast::Sender<LogStatusEvent>,
+    stats: &QueueStats,
+    log_ids: &[Uuid],
+) -> anyhow::Result<()> {
+    // Resolve ids to rows, skipping any that vanished; preserve order so the
+    // leaf layout is deterministic.
+    let mut logs = Vec::with_capacity(log_ids.len());
+    for &id in log_ids {
+        if let Some(log) = db.get_log_by_id(id).await? {
+            logs.push(log);
+        }
+    }
+    if logs.is_empty() {
+        return Ok(());
+    }
+
+    // Build the tree and each leaf's proof up front so a successful submission
+    // can be finalized immediately.
+    let mut leaves = Vec::with_capacity(logs.len());
+    for log in &logs {
+        leaves.push(models::decode_hash(&log.hash)?);
+    }
+    let ids: Vec<Uuid> = logs.iter().map(|l| l.id).collect();
+    let root = hex::encode(models::merkle_root(&leaves));
+    // Claim atomically: the batcher safety net and the reconciler scan the same
+    // `pending` rows, so a queue-full log the batcher also grabbed must be anchored
+    // by exactly one of us. Skip entirely if every candidate was claimed elsewhere.
+    let Some((batch, claimed)) = db.create_batch(&root, &ids).await? else {
+        return Ok(());
+    };
+
+    let mut backoff = BASE_BACKOFF;
+    for attempt in 1..=MAX_ATTEMPTS {
+        match solana.submit_log_root(&root).await {
+            Ok(signature) => {
+                let proofs: Vec<(Uuid, MerkleProof)> = logs
+                    .iter()
+                    .enumerate()
+                    .filter(|(_, log)| claimed.contains(&log.id))
+                    .map(|(i, log)| (log.id, models::merkle_proof(&leaves, i)))
+                    .collect();
+                db.finalize_batch(batch.id, &signature, &proofs).await?;
+                stats.submitted_session.fetch_add(claimed.len() as u64, Ordering::Relaxed);
+                for log in logs.iter().filter(|l| claimed.contains(&l.id)) {
+                    let _ = events.send(LogStatusEvent {
+                        log_id: log.id,
+                        event_type: log.event_type.clone(),
+                        severity: log.severity.clone(),
+                        status: "submitted".to_string(),
+                        tx_signature: Some(signature.clone()),
+                    });
+                }
+                return Ok(());
+            }
+            Err(e) if attempt == MAX_ATTEMPTS => {
+                log::error!("Batch of {} logs failed after {} attempts: {}", claimed.len(), MAX_ATTEMPTS, e);
+                db.fail_batch(batch.id).await?;
+                for log in logs.iter().filter(|l| claimed.contains(&l.id)) {
+                    let _ = events.send(LogStatusEvent {
+                        log_id: log.id,
+                        event_type: log.event_type.clone(),
+                        severity: log.severity.clone(),
+                        status: "failed".to_string(),
+                        tx_signature: None,
+                    });
+                }
+            }
+            Err(e) => {
+                log::warn!("Batch submit attempt {} failed, retrying in {:?}: {}", attempt, backoff, e);
+                tokio::time::sleep(backoff).await;
+                backoff = (backoff * 2).min(MAX_BACKOFF);
+            }
+        }
+    }
+    Ok(())
+}
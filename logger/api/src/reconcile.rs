@@ -0,0 +1,215 @@
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::Utc;
+use solana_sdk::signature::Signature;
+use tokio::sync::broadcast;
+
+use crate::db::Database;
+use crate::models::{self, LogStatusEvent, MerkleProof};
+use crate::backend::LogBackend;
+
+/// How often the reconciler sweeps for drifted logs.
+const SCAN_INTERVAL: Duration = Duration::from_secs(30);
+
+/// A log stuck `pending` this long is re-submitted directly.
+const STUCK_PENDING_SECS: i64 = 60;
+
+/// An unconfirmed `submitted` log older than this (past blockhash expiry) is
+/// treated as dropped and re-anchored.
+const EXPIRY_WINDOW_SECS: i64 = 120;
+
+/// A log left `batching` this long lost its flush to a crash; recover it.
+const STUCK_BATCHING_SECS: i64 = 120;
+
+/// How many logs of each kind to reconcile per sweep.
+const SCAN_LIMIT: i64 = 128;
+
+/// Base backoff for re-anchoring a `failed` log; doubles each attempt.
+const BASE_BACKOFF: Duration = Duration::from_millis(500);
+
+/// Upper bound on the exponential part of a backoff interval (jitter is added on top).
+const MAX_BACKOFF: Duration = Duration::from_secs(30);
+
+/// Attempts before a `failed` log is abandoned as permanently failed.
+const MAX_ATTEMPTS: i32 = 8;
+
+/// Running count of logs the reconciler has resubmitted since startup.
+pub type ReconcileStats = AtomicU64;
+
+/// Spawn the reconciliation worker.
+///
+/// Single subsystem recovering every way the happy path can drift: logs stuck
+/// in `pending` (submission task never ran) or `batching` (a crash between
+/// `create_batch` and finalize), `submitted` whose signature never landed, and
+/// `confirmed` whose anchor vanished after a reorg/expiry — plus `failed` logs,
+/// which it re-anchors with exponential backoff. Every recovery goes through the
+/// proof-producing anchor path and the resubmission count is surfaced in
+/// `get_stats`.
+pub fn spawn(
+    db: Arc<Database>,
+    solana: Arc<dyn LogBackend>,
+    events: broadcast::Sender<LogStatusEvent>,
+) -> Arc<ReconcileStats> {
+    let stats = Arc::new(ReconcileStats::new(0));
+    let worker_stats = Arc::clone(&stats);
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(SCAN_INTERVAL);
+        loop {
+            ticker.tick().await;
+            if let Err(e) = sweep(&db, &solana, &events, &worker_stats).await {
+                log::error!("Reconciliation sweep failed: {}", e);
+            }
+        }
+    });
+    stats
+}
+
+async fn sweep(
+    db: &Database,
+    solana: &dyn LogBackend,
+    events: &broadcast::Sender<LogStatusEvent>,
+    stats: &ReconcileStats,
+) -> anyhow::Result<()> {
+    // Stuck pending logs: never anchored, resubmit directly.
+    for log in db.get_stuck_pending_logs(STUCK_PENDING_SECS, SCAN_LIMIT).await? {
+        log::warn!("Reconciling stuck pending log {}", log.id);
+        if let Err(e) = anchor_with_proof(db, solana, events, stats, &log).await {
+            log::error!("Failed to re-anchor stuck pending log {}: {}", log.id, e);
+        }
+    }
+
+    // Stranded batches: a crash between create_batch and finalize/fail leaves
+    // logs in `batching`, which no other scan selects. Fail the batch so its
+    // logs fall back into the `failed` retry path below.
+    for batch_id in db.get_stuck_batching_batches(STUCK_BATCHING_SECS, SCAN_LIMIT).await? {
+        log::warn!("Recovering stranded batch {}", batch_id);
+        db.fail_batch(batch_id).await?;
+    }
+
+    // Stale submitted logs: verify the signature still resolves, else demote to
+    // `failed` and let the single claim-gated retry path below re-anchor it. Going
+    // through `failed` keeps re-anchoring in one place, so the confirm poller (which
+    // also flags dropped signatures `failed`) and this sweep can't both re-submit
+    // the same dropped tx.
+    for log in db.get_stale_submitted_logs(EXPIRY_WINDOW_SECS, SCAN_LIMIT).await? {
+        let landed = match &log.tx_signature {
+            Some(sig) => signature_resolves(solana, sig).await,
+            None => false,
+        };
+        if !landed {
+            if let Some(sig) = &log.tx_signature {
+                log::warn!("Signature for log {} never landed; marking failed", log.id);
+                db.update_status_by_signature(sig, "failed").await?;
+            }
+        }
+    }
+
+    // Confirmed logs whose anchor has since vanished (reorg/expiry): if the stored
+    // signature no longer resolves even with history search, demote to `failed` so
+    // the retry path re-anchors it exactly once.
+    for log in db.get_confirmed_logs_to_recheck(SCAN_LIMIT).await? {
+        let landed = match &log.tx_signature {
+            Some(sig) => signature_resolves(solana, sig).await,
+            None => false,
+        };
+        if !landed {
+            if let Some(sig) = &log.tx_signature {
+                log::warn!("Confirmed log {} no longer resolves on-chain; marking failed", log.id);
+                db.update_status_by_signature(sig, "failed").await?;
+            }
+        }
+    }
+
+    // Failed logs: re-anchor with exponential backoff, persisting the schedule so
+    // a restart resumes rather than retrying everything at once.
+    for log in db.get_retryable_logs(SCAN_LIMIT).await? {
+        let attempt = log.attempt_count + 1;
+        match anchor_with_proof(db, solana, events, stats, &log).await {
+            Ok(()) => log::info!("Retry anchored log {} (attempt {})", log.id, attempt),
+            Err(e) if attempt >= MAX_ATTEMPTS => {
+                log::error!("Log {} exhausted {} retries, leaving failed: {}", log.id, MAX_ATTEMPTS, e);
+                db.schedule_log_retry(log.id, attempt, Utc::now() + chrono::Duration::days(3650)).await?;
+            }
+            Err(e) => {
+                let delay = backoff_delay(attempt, &log.id);
+                log::warn!("Log {} retry {} failed, next in {:?}: {}", log.id, attempt, delay, e);
+                let next = Utc::now() + chrono::Duration::from_std(delay).unwrap_or(chrono::Duration::seconds(30));
+                db.schedule_log_retry(log.id, attempt, next).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Check whether a signature is still known to the cluster.
+///
+/// Uses history search so a signature that merely aged out of the status cache
+/// (the normal fate of a confirmed/finalized tx) still counts as resolved; only
+/// a genuinely unknown signature is treated as dropped.
+async fn signature_resolves(solana: &dyn LogBackend, sig: &str) -> bool {
+    match Signature::from_str(sig) {
+        Ok(signature) => match solana.get_signature_statuses_with_history(&[signature]).await {
+            Ok(statuses) => statuses.into_iter().next().flatten().is_some(),
+            Err(_) => true, // On RPC error assume it landed; don't double-anchor.
+        },
+        Err(_) => false,
+    }
+}
+
+/// Re-anchor a single log through the proof-producing path and record it.
+///
+/// Anchors the log as a single-leaf Merkle batch (root == leaf) via
+/// `create_batch`/`submit_log_root`/`finalize_batch`, matching the queue and
+/// batcher, so the re-anchored log is stamped with the inclusion proof
+/// `verify_log` requires. On submission failure the batch (and its log) are
+/// marked `failed` and the error is returned so the caller can schedule backoff.
+async fn anchor_with_proof(
+    db: &Database,
+    solana: &dyn LogBackend,
+    events: &broadcast::Sender<LogStatusEvent>,
+    stats: &ReconcileStats,
+    log: &crate::models::LogEntry,
+) -> anyhow::Result<()> {
+    let leaf = models::decode_hash(&log.hash)?;
+    let root = hex::encode(models::merkle_root(&[leaf]));
+    let proof: MerkleProof = models::merkle_proof(&[leaf], 0);
+    // Claim atomically: if another producer already moved this log past
+    // `pending`/`failed`, it is no longer ours to re-anchor, so bow out quietly.
+    let Some((batch, _claimed)) = db.create_batch(&root, &[log.id]).await? else {
+        return Ok(());
+    };
+
+    match solana.submit_log_root(&root).await {
+        Ok(signature) => {
+            db.finalize_batch(batch.id, &signature, &[(log.id, proof)]).await?;
+            stats.fetch_add(1, Ordering::Relaxed);
+            let _ = events.send(LogStatusEvent {
+                log_id: log.id,
+                event_type: log.event_type.clone(),
+                severity: log.severity.clone(),
+                status: "submitted".to_string(),
+                tx_signature: Some(signature),
+            });
+            Ok(())
+        }
+        Err(e) => {
+            db.fail_batch(batch.id).await?;
+            Err(e)
+        }
+    }
+}
+
+/// Exponential backoff capped at [`MAX_BACKOFF`], plus deterministic per-log jitter.
+///
+/// Jitter is derived from the log id so it needs no RNG yet still spreads retries
+/// of many logs across the window instead of bunching them on the same tick.
+fn backoff_delay(attempt: i32, log_id: &sqlx::types::Uuid) -> Duration {
+    let exp = BASE_BACKOFF.saturating_mul(1u32 << attempt.clamp(0, 16) as u32);
+    let capped = exp.min(MAX_BACKOFF);
+    let jitter_ms = (log_id.as_bytes()[0] as u64) * 2; // 0..=510ms
+    capped + Duration::from_millis(jitter_ms)
+}
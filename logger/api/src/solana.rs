@@ -43,7 +43,7 @@ impl SolanaClient {
     }
 
     /// Load keypair from JSON file
-    fn load_keypair(path: &str) -> Result<Keypair> {
+    pub(crate) fn load_keypair(path: &str) -> Result<Keypair> {
         let expanded_path = shellexpand::tilde(path);
         let keypair_bytes = std::fs::read(expanded_path.as_ref())
             .context("Failed to read keypair file")?;
@@ -98,6 +98,84 @@ impl SolanaClient {
         Ok(signature.to_string())
     }
 
+    /// Anchor a Merkle root on-chain via the memo instruction.
+    ///
+    /// Mirrors [`submit_log_hash`] but tags the payload as `LOG_ROOT:<hex_root>`
+    /// so a single transaction covers a whole batch of logs.
+    pub async fn submit_log_root(&self, hex_root: &str) -> Result<String> {
+        let recent_blockhash = self.rpc_client
+            .get_latest_blockhash()
+            .context("Failed to get recent blockhash")?;
+
+        let memo_data = format!("LOG_ROOT:{}", hex_root);
+
+        let memo_program_id = Pubkey::from_str("MemoSq4gqABAXKb96qnH8TysNcWxMyWCqXgDLGmfcHr")
+            .unwrap();
+
+        let instruction = Instruction {
+            program_id: memo_program_id,
+            accounts: vec![],
+            data: memo_data.into_bytes(),
+        };
+
+        let transaction = Transaction::new_signed_with_payer(
+            &[instruction],
+            Some(&self.keypair.pubkey()),
+            &[&self.keypair],
+            recent_blockhash,
+        );
+
+        let signature = self.rpc_client
+            .send_and_confirm_transaction(&transaction)
+            .context("Failed to send transaction")?;
+
+        Ok(signature.to_string())
+    }
+
+    /// Recover the Merkle root anchored by a batch transaction.
+    ///
+    /// The root is read out of the cleanly decoded `spl-memo` instruction
+    /// (`LOG_ROOT:<hex>`) rather than by substring-scanning `log_messages`, so
+    /// the verification decision rests on the parsed instruction payload.
+    pub async fn get_log_root(&self, tx_signature: &str) -> Result<Option<String>> {
+        let signature = Signature::from_str(tx_signature)
+            .context("Invalid transaction signature")?;
+
+        Ok(self
+            .fetch_memo(&signature)?
+            .and_then(|memo| memo.strip_prefix("LOG_ROOT:").map(|r| r.to_string())))
+    }
+
+    /// Decode the `spl-memo` payload from a confirmed transaction, if present.
+    ///
+    /// Requests the JSON-parsed form and walks the parsed instruction list so
+    /// callers work against the structured memo rather than raw log lines.
+    fn fetch_memo(&self, signature: &Signature) -> Result<Option<String>> {
+        use solana_transaction_status::{EncodedTransaction, UiMessage, UiInstruction, UiParsedInstruction};
+
+        let tx = self.rpc_client
+            .get_transaction(signature, solana_client::rpc_config::RpcTransactionConfig {
+                encoding: Some(solana_transaction_status::UiTransactionEncoding::JsonParsed),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            })
+            .context("Failed to fetch transaction")?;
+
+        if let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction {
+            if let UiMessage::Parsed(msg) = &ui_tx.message {
+                for ix in &msg.instructions {
+                    if let UiInstruction::Parsed(UiParsedInstruction::Parsed(parsed)) = ix {
+                        if parsed.program == "spl-memo" {
+                            return Ok(parsed.parsed.as_str().map(|s| s.to_string()));
+                        }
+                    }
+                }
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Verify log hash exists on blockchain
     pub async fn verify_log_hash(&self, tx_signature: &str) -> Result<Option<String>> {
         let signature = Signature::from_str(tx_signature)
@@ -133,6 +211,99 @@ impl SolanaClient {
         Ok(None)
     }
 
+    /// Fetch confirmation status for up to ~256 signatures in a single call.
+    ///
+    /// Thin wrapper over Solana's `getSignatureStatuses`; the returned vector is
+    /// positionally aligned with `signatures`, and `None` entries mark
+    /// signatures missing from the recent status cache: dropped/expired ones,
+    /// but also ones that have merely aged out of it.
+    pub async fn get_signature_statuses(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<solana_transaction_status::TransactionStatus>>> {
+        let statuses = self.rpc_client
+            .get_signature_statuses(signatures)
+            .context("Failed to fetch signature statuses")?;
+        Ok(statuses.value)
+    }
+
+    /// Like [`get_signature_statuses`] but searches the full transaction history.
+    ///
+    /// The plain call only consults the ~150-block status cache, so a finalized
+    /// transaction that has aged out returns `None`. This variant sets
+    /// `searchTransactionHistory`, so a `None` here means the signature is truly
+    /// unknown (dropped/expired) rather than merely old.
+    pub async fn get_signature_statuses_with_history(
+        &self,
+        signatures: &[Signature],
+    ) -> Result<Vec<Option<solana_transaction_status::TransactionStatus>>> {
+        let statuses = self.rpc_client
+            .get_signature_statuses_with_history(signatures)
+            .context("Failed to fetch signature statuses with history")?;
+        Ok(statuses.value)
+    }
+
+    /// Decode a confirmed anchoring transaction into auditable metadata.
+    ///
+    /// Requests the JSON-parsed form so the memo is read out of the parsed
+    /// instruction list rather than by substring-scanning log lines, and
+    /// surfaces slot, block time, fee and signer alongside it.
+    pub async fn get_transaction_detail(
+        &self,
+        tx_signature: &str,
+    ) -> Result<Option<crate::models::TransactionDetail>> {
+        use solana_transaction_status::{EncodedTransaction, UiMessage};
+
+        let signature = Signature::from_str(tx_signature)
+            .context("Invalid transaction signature")?;
+
+        let tx = self.rpc_client
+            .get_transaction(&signature, solana_client::rpc_config::RpcTransactionConfig {
+                encoding: Some(solana_transaction_status::UiTransactionEncoding::JsonParsed),
+                commitment: Some(CommitmentConfig::confirmed()),
+                max_supported_transaction_version: Some(0),
+            })
+            .context("Failed to fetch transaction")?;
+
+        let meta = tx.transaction.meta.as_ref();
+        let fee = meta.map(|m| m.fee);
+
+        // Decode the memo via the parsed instruction list (same path as
+        // `get_log_root`), and read the fee payer and blockhash off the message.
+        let memo = self.fetch_memo(&signature)?;
+        let mut signer = None;
+        let mut recent_blockhash = None;
+        if let EncodedTransaction::Json(ui_tx) = &tx.transaction.transaction {
+            if let UiMessage::Parsed(msg) = &ui_tx.message {
+                signer = msg.account_keys.first().map(|k| k.pubkey.clone());
+                recent_blockhash = Some(msg.recent_blockhash.clone());
+            }
+        }
+
+        // A follow-up status lookup reports the live confirmation count/level.
+        let (confirmations, confirmation_status) = match self.rpc_client.get_signature_statuses(&[signature]) {
+            Ok(resp) => match resp.value.into_iter().next().flatten() {
+                Some(status) => (
+                    status.confirmations,
+                    status.confirmation_status.map(|c| format!("{:?}", c).to_lowercase()),
+                ),
+                None => (None, Some("confirmed".to_string())),
+            },
+            Err(_) => (None, Some("confirmed".to_string())),
+        };
+
+        Ok(Some(crate::models::TransactionDetail {
+            slot: tx.slot,
+            block_time: tx.block_time,
+            confirmation_status,
+            confirmations,
+            fee,
+            signer,
+            recent_blockhash,
+            memo,
+        }))
+    }
+
     /// Get account balance
     pub fn get_balance(&self) -> Result<u64> {
         let balance = self.rpc_client
@@ -146,22 +317,3 @@ impl SolanaClient {
         self.keypair.pubkey()
     }
 }
-
-// For testing without actual blockchain
-#[cfg(test)]
-pub struct MockSolanaClient;
-
-#[cfg(test)]
-impl MockSolanaClient {
-    pub fn new() -> Self {
-        Self
-    }
-
-    pub async fn submit_log_hash(&self, _hash: &str) -> Result<String> {
-        Ok("mock_signature_12345".to_string())
-    }
-
-    pub async fn verify_log_hash(&self, _tx_signature: &str) -> Result<Option<String>> {
-        Ok(Some("mock_hash_67890".to_string()))
-    }
-}